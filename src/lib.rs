@@ -1,4 +1,2 @@
-#![feature(is_some_and)] // used in sta EntryParser::build_packed
-#![feature(drain_filter)] // used in sta::plan::PlanBuilder
-mod rtn;
+pub mod rtn;
 pub mod sta;
@@ -1,2 +1,23 @@
-mod removals;
-mod returns;
+pub mod reconcile;
+pub mod removals;
+pub mod returns;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+/**
+Parses the date portion of a date string found within Amazon's reports.
+
+Accepts RFC 3339 timestamps (`2022-11-10T08:15:00+00:00`), timestamps
+without an offset (`2022-11-10T08:15:00`), and plain dates (`2022-11-10`).
+Returns `None` if the string matches none of these.
+*/
+fn parse_report_date(date: &str) -> Option<NaiveDate> {
+    let date = date.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(date) {
+        return Some(dt.date_naive());
+    };
+    if let Ok(dt) = NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S") {
+        return Some(dt.date());
+    };
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
@@ -0,0 +1,120 @@
+#![allow(dead_code)]
+use super::removals::CsvRemShipParser;
+use super::returns::ReturnsBucket;
+use std::collections::BTreeMap;
+
+/// The shipped and returned unit quantities of a single removal order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderReconciliation {
+    order_id: String,
+    shipped: u32,
+    returned: u32,
+}
+impl OrderReconciliation {
+    /// Returns a reference to the order id of this [`OrderReconciliation`].
+    pub fn get_order_id(&self) -> &str {
+        &self.order_id
+    }
+    /// Returns the units shipped out by Amazon for this order.
+    pub fn get_shipped(&self) -> u32 {
+        self.shipped
+    }
+    /// Returns the units received back for this order.
+    pub fn get_returned(&self) -> u32 {
+        self.returned
+    }
+    /**
+    Returns `shipped - returned`.
+
+    A positive delta means units are still unaccounted for, a negative
+    delta means more units came back than were shipped.
+    */
+    pub fn delta(&self) -> i64 {
+        self.shipped as i64 - self.returned as i64
+    }
+}
+
+/// The result of joining removal shipments against customer returns.
+#[derive(Debug, Default, Clone)]
+pub struct Reconciliation {
+    orders: Vec<OrderReconciliation>,
+}
+impl Reconciliation {
+    /// Returns every reconciled order, sorted by order id.
+    pub fn orders(&self) -> &[OrderReconciliation] {
+        &self.orders
+    }
+    /**
+    Returns the units shipped but not yet returned, across every order.
+
+    Orders that returned more than they shipped do not offset the others.
+    */
+    pub fn missing_units(&self) -> u32 {
+        self.orders
+            .iter()
+            .map(|order| order.shipped.saturating_sub(order.returned))
+            .fold(0u32, |total, units| total.saturating_add(units))
+    }
+}
+
+/**
+Joins removal shipments against customer returns on the order id.
+
+Each removal order appears once, with the shipped quantity summed across
+every row (and therefore every tracking number) of that order. Returns
+that share no order id with a removal are ignored.
+*/
+pub fn reconcile(removals: &[CsvRemShipParser], returns: &ReturnsBucket) -> Reconciliation {
+    let mut orders = BTreeMap::new();
+    for removal in removals {
+        let order = orders
+            .entry(removal.get_order_id().to_string())
+            .or_insert((0u32, 0u32));
+        order.0 = order.0.saturating_add(removal.get_shipped_quantity());
+    }
+    for cr in returns {
+        if let Some(order) = orders.get_mut(cr.get_order_id()) {
+            order.1 = order.1.saturating_add(cr.get_units());
+        };
+    }
+    let orders = orders
+        .into_iter()
+        .map(|(order_id, (shipped, returned))| OrderReconciliation {
+            order_id,
+            shipped,
+            returned,
+        })
+        .collect::<Vec<OrderReconciliation>>();
+    Reconciliation { orders }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    static TEST_REMOVAL_SHIPMENT_RECORD: &str = "tests/data/RemovalShipments.csv";
+    static TEST_CUSTOMER_RETURN_RECORD: &str = "tests/data/CustomerReturns.csv";
+
+    #[test]
+    fn reconcile_removals_with_returns() {
+        let removals = CsvRemShipParser::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let returns = ReturnsBucket::from_csv_path(TEST_CUSTOMER_RETURN_RECORD).unwrap();
+        let reconciliation = reconcile(&removals, &returns);
+
+        let orders = reconciliation
+            .orders()
+            .iter()
+            .map(|o| (o.get_order_id(), o.get_shipped(), o.get_returned()))
+            .collect::<Vec<(&str, u32, u32)>>();
+        assert_eq!(
+            orders,
+            vec![
+                ("RMV-0001", 14, 6),
+                ("RMV-0002", 3, 1),
+                ("RMV-0003", 2, 0),
+                ("RMV-0004", 6, 0),
+            ]
+        );
+        assert_eq!(reconciliation.orders()[0].delta(), 8);
+        assert_eq!(reconciliation.missing_units(), 18);
+    }
+}
@@ -1,52 +1,132 @@
 #![allow(dead_code)]
+use super::parse_report_date;
+use chrono::NaiveDate;
+use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+/// The column headers of Amazon's Removal Shipment report, in order.
+const REMOVAL_HEADERS: [&str; 10] = [
+    "request-date",
+    "order-id",
+    "shipment-date",
+    "sku",
+    "fnsku",
+    "disposition",
+    "shipped-quantity",
+    "carrier",
+    "tracking-number",
+    "removal-order-type",
+];
 /**
 Csv **Rem**oval **Ship**ment Parser
 
 Helper for dealing with Amazon's Removal Shipment reports.
 This structure accounts for a single row within the report.
+
+Fields are declared in the order of [`REMOVAL_HEADERS`], so a serialized
+row lines up with the report's columns.
 */
 #[derive(Deserialize, Serialize, Default, Debug, Clone)]
-struct CsvRemShipParser {
-    #[serde(alias = "carrier")]
-    carrier: String,
-    #[serde(alias = "disposition")]
-    disposition: String,
-    #[serde(alias = "fnsku")]
-    fnsku: String,
-    #[serde(alias = "sku")]
-    merchant_sku: String,
-    #[serde(alias = "order-id")]
-    order_id: String,
-    #[serde(alias = "removal-order-type")]
-    removal_type: String,
+pub struct CsvRemShipParser {
     #[serde(alias = "request-date")]
     request_date: String,
+    #[serde(alias = "order-id")]
+    order_id: String,
     #[serde(alias = "shipment-date")]
     shipment_date: String,
+    #[serde(alias = "sku")]
+    merchant_sku: String,
+    #[serde(alias = "fnsku")]
+    fnsku: String,
+    #[serde(alias = "disposition")]
+    disposition: String,
     #[serde(alias = "shipped-quantity")]
     shipped_quantity: u32,
+    #[serde(alias = "carrier")]
+    carrier: String,
     #[serde(alias = "tracking-number")]
     tracking: String,
+    #[serde(alias = "removal-order-type")]
+    removal_type: String,
 }
 impl CsvRemShipParser {
+    /**
+    Parses every row of a Removal Shipment report.
+
+    # Errors
+
+    This function will error if it comes across any issue that may arise during
+    general IO / CSV reading. See [`csv::Error`].
+    */
+    pub fn from_csv_path<P>(path: P) -> Result<Vec<Self>, csv::Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_csv_path_with_delimiter(path, b',')
+    }
+    /**
+    Parses every row of a Removal Shipment report using `delimiter` to
+    separate fields, e.g. `b'\t'` for Amazon's `.txt` reports.
+
+    # Errors
+
+    This function will error if it comes across any issue that may arise during
+    general IO / CSV reading. See [`csv::Error`].
+    */
+    pub fn from_csv_path_with_delimiter<P>(path: P, delimiter: u8) -> Result<Vec<Self>, csv::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let rdr = ReaderBuilder::new().delimiter(delimiter).from_path(path)?;
+        Self::from_csv_reader(rdr)
+    }
+    fn from_csv_reader<R: std::io::Read>(mut rdr: csv::Reader<R>) -> Result<Vec<Self>, csv::Error> {
+        rdr.records()
+            .map(|row| Self::from_csv_record(row?))
+            .collect()
+    }
     fn from_csv_record(csv_record: csv::StringRecord) -> Result<Self, csv::Error> {
-        let hdr = vec![
-            "request-date",
-            "order-id",
-            "shipment-date",
-            "sku",
-            "fnsku",
-            "disposition",
-            "shipped-quantity",
-            "carrier",
-            "tracking-number",
-            "removal-order-type",
-        ];
-        let hdr_str = csv::StringRecord::from(hdr);
+        let hdr_str = csv::StringRecord::from(REMOVAL_HEADERS.to_vec());
         csv_record.deserialize(Some(&hdr_str))
     }
+    /// Returns a reference to the order id of this [`CsvRemShipParser`].
+    pub fn get_order_id(&self) -> &str {
+        &self.order_id
+    }
+    /// Returns the shipped quantity of this [`CsvRemShipParser`].
+    pub fn get_shipped_quantity(&self) -> u32 {
+        self.shipped_quantity
+    }
+    /**
+    Returns the request date of this [`CsvRemShipParser`].
+
+    Both date-only and date-time forms are accepted, returning `None` if
+    the date cannot be parsed.
+    */
+    pub fn try_request_date(&self) -> Option<NaiveDate> {
+        parse_report_date(&self.request_date)
+    }
+    /**
+    Returns the shipment date of this [`CsvRemShipParser`].
+
+    Both date-only and date-time forms are accepted, returning `None` if
+    the date cannot be parsed.
+    */
+    pub fn try_shipment_date(&self) -> Option<NaiveDate> {
+        parse_report_date(&self.shipment_date)
+    }
+    /**
+    Returns the number of days between the removal being requested and
+    shipped.
+
+    Returns `None` if either date cannot be parsed, or if the shipment date
+    precedes the request date.
+    */
+    pub fn try_transit_days(&self) -> Option<i64> {
+        let days = (self.try_shipment_date()? - self.try_request_date()?).num_days();
+        (days >= 0).then_some(days)
+    }
     /**
     Splits tracking by '`,`'. Returning the entire string if there is no '`,`'
 
@@ -62,21 +142,261 @@ impl CsvRemShipParser {
             .collect::<Vec<_>>()
     }
 }
+/// The owning iterator that is produced by the [`RemovalReport`] struct.
+#[derive(Debug)]
+pub struct RemovalReportIter(std::vec::IntoIter<CsvRemShipParser>);
+
+impl Iterator for RemovalReportIter {
+    type Item = CsvRemShipParser;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/**
+A container of removal shipment records.
+
+Iterate over this struct to reach the contained data.
+*/
+#[derive(Default, Debug)]
+pub struct RemovalReport {
+    vec: Vec<CsvRemShipParser>,
+}
+
+impl IntoIterator for RemovalReport {
+    type Item = CsvRemShipParser;
+    type IntoIter = RemovalReportIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RemovalReportIter(self.vec.into_iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a RemovalReport {
+    type Item = &'a CsvRemShipParser;
+    type IntoIter = std::slice::Iter<'a, CsvRemShipParser>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.iter()
+    }
+}
+
+impl RemovalReport {
+    /// Creates a new [`RemovalReport`].
+    pub fn new(vec: Vec<CsvRemShipParser>) -> Self {
+        Self { vec }
+    }
+    /// Push an item onto the [`RemovalReport`].
+    pub fn push(&mut self, parser: CsvRemShipParser) {
+        self.vec.push(parser)
+    }
+    /// Returns the number of rows in the [`RemovalReport`].
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+    /// Returns `true` if the [`RemovalReport`] contains no rows.
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+    /**
+    Returns the shipped units for each FNSKU.
+
+    FNSKUs are trimmed and uppercased. Sums saturate at [`u32::MAX`].
+    */
+    pub fn units_by_fnsku(&self) -> HashMap<String, u32> {
+        let mut map = HashMap::new();
+        for parser in &self.vec {
+            let fnsku = parser.fnsku.trim().to_uppercase();
+            let units = map.entry(fnsku).or_insert(0u32);
+            *units = units.saturating_add(parser.shipped_quantity);
+        }
+        map
+    }
+    /**
+    Returns the shipped units for each disposition.
+
+    Dispositions are trimmed and uppercased, so `"Sellable"` and
+    `"sellable"` are counted together. Sums saturate at [`u32::MAX`].
+    */
+    pub fn units_by_disposition(&self) -> HashMap<String, u32> {
+        let mut map = HashMap::new();
+        for parser in &self.vec {
+            let disposition = parser.disposition.trim().to_uppercase();
+            let units = map.entry(disposition).or_insert(0u32);
+            *units = units.saturating_add(parser.shipped_quantity);
+        }
+        map
+    }
+    /**
+    Writes every row to `w` as a Removal Shipment report, headed by
+    [`REMOVAL_HEADERS`].
+
+    The output can be read back with [`RemovalReport::from_csv_path`].
+
+    # Errors
+
+    This function will return an error if writing to `w` fails.
+    */
+    pub fn to_csv<W: std::io::Write>(&self, w: W) -> csv::Result<()> {
+        let mut wtr = csv::WriterBuilder::new().has_headers(false).from_writer(w);
+        wtr.write_record(REMOVAL_HEADERS)?;
+        for parser in &self.vec {
+            wtr.serialize(parser)?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+    /**
+    Creates a [`RemovalReport`] from a Removal Shipment report.
+
+    # Errors
+
+    This function will error if it comes across any issue that may arise during
+    general IO / CSV reading. See [`csv::Error`].
+    */
+    pub fn from_csv_path<P>(path: P) -> Result<Self, csv::Error>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_csv_path_with_delimiter(path, b',')
+    }
+    /**
+    Creates a [`RemovalReport`] from a Removal Shipment report using
+    `delimiter` to separate fields, e.g. `b'\t'` for Amazon's `.txt` reports.
+
+    # Errors
+
+    This function will error if it comes across any issue that may arise during
+    general IO / CSV reading. See [`csv::Error`].
+    */
+    pub fn from_csv_path_with_delimiter<P>(path: P, delimiter: u8) -> Result<Self, csv::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let vec = CsvRemShipParser::from_csv_path_with_delimiter(path, delimiter)?;
+        Ok(Self::new(vec))
+    }
+    /**
+    Creates a [`RemovalReport`] from the contents of a Removal Shipment
+    report, such as an inline string or a report piped through stdin.
+
+    # Errors
+
+    This function will error if the CSV format is incorrect. See
+    [`csv::Error`].
+    */
+    pub fn from_csv_str(s: &str) -> Result<Self, csv::Error> {
+        let vec = CsvRemShipParser::from_csv_reader(csv::Reader::from_reader(s.as_bytes()))?;
+        Ok(Self::new(vec))
+    }
+    /**
+    Creates a [`RemovalReport`] from any reader of a Removal Shipment report
+    using `delimiter` to separate fields, e.g. `b'\t'` for Amazon's `.txt`
+    reports.
+
+    # Errors
+
+    This function will error if reading fails or the CSV format is
+    incorrect. See [`csv::Error`].
+    */
+    pub fn from_reader_with_delimiter<R>(reader: R, delimiter: u8) -> Result<Self, csv::Error>
+    where
+        R: std::io::Read,
+    {
+        let rdr = ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(reader);
+        Ok(Self::new(CsvRemShipParser::from_csv_reader(rdr)?))
+    }
+}
+/**
+Returns every distinct tracking number within a Removal Shipment report.
+
+Tracking numbers are split and trimmed per row, empty strings are
+dropped, and the result is sorted.
+*/
+pub fn unique_tracking(parsers: &[CsvRemShipParser]) -> Vec<String> {
+    let tracking = parsers
+        .iter()
+        .flat_map(|parser| parser.split_tracking_numbers())
+        .filter(|tracking| !tracking.is_empty())
+        .collect::<HashSet<&str>>();
+    let mut tracking = tracking
+        .into_iter()
+        .map(|tracking| tracking.to_string())
+        .collect::<Vec<String>>();
+    tracking.sort_unstable();
+    tracking
+}
+/**
+Returns the shipped units of a Removal Shipment report, summed by carrier.
+
+Carrier names are trimmed and uppercased, so `"UPS"` and `"ups "` are
+counted together.
+*/
+pub fn units_by_carrier(parsers: &[CsvRemShipParser]) -> HashMap<String, u32> {
+    let mut units = HashMap::new();
+    for parser in parsers {
+        let carrier = parser.carrier.trim().to_uppercase();
+        let total = units.entry(carrier).or_insert(0u32);
+        *total = total.saturating_add(parser.shipped_quantity);
+    }
+    units
+}
+/// Returns the shipped units of a Removal Shipment report, summed by removal type.
+pub fn units_by_removal_type(parsers: &[CsvRemShipParser]) -> HashMap<String, u32> {
+    let mut units = HashMap::new();
+    for parser in parsers {
+        let total = units.entry(parser.removal_type.to_string()).or_insert(0u32);
+        *total = total.saturating_add(parser.shipped_quantity);
+    }
+    units
+}
 #[cfg(test)]
 mod test {
     use super::*;
     use csv::Reader;
     fn load_rem_shipment_report_csv() -> Vec<CsvRemShipParser> {
         static TEST_REMOVAL_SHIPMENT_RECORD: &str = "tests/data/RemovalShipments.csv";
-        let rdr = Reader::from_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
-        rdr.into_records()
-            .filter_map(|wrapped_row| {
-                let Ok(row) = wrapped_row else {
-                return None
-            };
-                CsvRemShipParser::from_csv_record(row).ok()
-            })
-            .collect::<Vec<CsvRemShipParser>>()
+        let report = RemovalReport::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        report.into_iter().collect::<Vec<CsvRemShipParser>>()
+    }
+    #[test]
+    fn load_removal_report() {
+        static TEST_REMOVAL_SHIPMENT_RECORD: &str = "tests/data/RemovalShipments.csv";
+        let mut report = RemovalReport::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        assert!(!report.is_empty());
+        assert_eq!(report.len(), 5);
+        assert_eq!((&report).into_iter().count(), report.len());
+        report.push(CsvRemShipParser::default());
+        assert_eq!(report.len(), 6);
+    }
+    #[test]
+    fn removal_report_from_csv_str() {
+        let csv = "\
+request-date,order-id,shipment-date,sku,fnsku,disposition,shipped-quantity,carrier,tracking-number,removal-order-type
+2022-11-01,RMV-0001,2022-11-04,MSKU-1,X001AAAAA1,Sellable,10,UPS,1Z0000000000000001,Return
+";
+        let report = RemovalReport::from_csv_str(csv).unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report.units_by_fnsku().get("X001AAAAA1"), Some(&10));
+    }
+    #[test]
+    fn removal_report_to_csv_round_trip() {
+        static TEST_REMOVAL_SHIPMENT_RECORD: &str = "tests/data/RemovalShipments.csv";
+        let report = RemovalReport::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let mut buf = Vec::new();
+        report.to_csv(&mut buf).unwrap();
+
+        let csv = String::from_utf8(buf).unwrap();
+        assert!(csv.starts_with(&REMOVAL_HEADERS.join(",")));
+        let reloaded = RemovalReport::from_csv_str(&csv).unwrap();
+        assert_eq!(reloaded.len(), report.len());
+        let first = reloaded.vec.first().unwrap();
+        assert_eq!(first.tracking, report.vec[0].tracking);
+        assert_eq!(first.get_order_id(), "RMV-0001");
+        assert_eq!(first.try_transit_days(), Some(3));
     }
     #[test]
     fn load_removal_shipment_csv() {
@@ -86,11 +406,81 @@ mod test {
             let Ok(row) = item else {
                 continue;
             };
-            match CsvRemShipParser::from_csv_record(row) {
-                Ok(_) => assert!(true),
-                Err(_) => assert!(false),
-            };
+            assert!(CsvRemShipParser::from_csv_record(row).is_ok());
+        }
+    }
+    #[test]
+    fn load_tab_delimited_removals() {
+        static TEST_REMOVAL_SHIPMENT_RECORD: &str = "tests/data/RemovalShipments.csv";
+        let mut rdr = Reader::from_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let mut wtr = csv::WriterBuilder::new()
+            .delimiter(b'\t')
+            .from_writer(Vec::new());
+        wtr.write_record(rdr.headers().unwrap()).unwrap();
+        for row in rdr.records() {
+            wtr.write_record(&row.unwrap()).unwrap();
         }
+        let tsv = wtr.into_inner().unwrap();
+
+        let report = RemovalReport::from_reader_with_delimiter(tsv.as_slice(), b'\t').unwrap();
+        let expected = CsvRemShipParser::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        assert_eq!(report.len(), expected.len());
+    }
+    #[test]
+    fn unique_tracking_numbers() {
+        let vrp = load_rem_shipment_report_csv();
+        let tracking = unique_tracking(&vrp);
+        assert!(!tracking.is_empty());
+        assert!(tracking.iter().all(|t| !t.is_empty()));
+        let distinct = tracking.iter().collect::<HashSet<_>>();
+        assert_eq!(distinct.len(), tracking.len());
+    }
+    #[test]
+    fn units_by_carrier_and_removal_type() {
+        let vrp = load_rem_shipment_report_csv();
+        let shipped = vrp.iter().map(|p| p.get_shipped_quantity()).sum::<u32>();
+
+        let by_carrier = units_by_carrier(&vrp);
+        assert_eq!(by_carrier.values().sum::<u32>(), shipped);
+        assert_eq!(by_carrier.get("UPS"), Some(&20));
+        assert!(!by_carrier.contains_key("ups "));
+
+        let by_type = units_by_removal_type(&vrp);
+        assert_eq!(by_type.values().sum::<u32>(), shipped);
+        assert_eq!(by_type.get("Disposal"), Some(&3));
+    }
+    #[test]
+    fn report_units_by_fnsku_and_disposition() {
+        static TEST_REMOVAL_SHIPMENT_RECORD: &str = "tests/data/RemovalShipments.csv";
+        let report = RemovalReport::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let shipped = (&report)
+            .into_iter()
+            .map(|p| p.get_shipped_quantity())
+            .sum::<u32>();
+
+        let by_fnsku = report.units_by_fnsku();
+        assert_eq!(by_fnsku.values().sum::<u32>(), shipped);
+        assert_eq!(by_fnsku.get("X001AAAAA1"), Some(&12));
+
+        let by_disposition = report.units_by_disposition();
+        assert_eq!(by_disposition.values().sum::<u32>(), shipped);
+        assert_eq!(by_disposition.get("SELLABLE"), Some(&20));
+        assert_eq!(by_disposition.get("UNSELLABLE"), Some(&5));
+    }
+    #[test]
+    fn transit_days() {
+        let vrp = load_rem_shipment_report_csv();
+        let mut rem = vrp.first().unwrap().clone();
+        assert_eq!(rem.try_request_date(), NaiveDate::from_ymd_opt(2022, 11, 1));
+        assert_eq!(rem.try_transit_days(), Some(3));
+        // RMV-0004 shipped the day it was requested
+        assert_eq!(vrp.last().unwrap().try_transit_days(), Some(0));
+
+        rem.shipment_date = "2022-10-30".to_string();
+        assert_eq!(rem.try_transit_days(), None);
+        rem.shipment_date = "not a date".to_string();
+        assert_eq!(rem.try_shipment_date(), None);
+        assert_eq!(rem.try_transit_days(), None);
     }
     #[test]
     fn split_tracking_numbers() {
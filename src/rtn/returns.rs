@@ -1,10 +1,37 @@
 #![allow(dead_code)]
-use csv::Reader;
-use serde::Deserialize;
+use super::parse_report_date;
+use super::removals::RemovalReport;
+use crate::sta::plan::Plan;
+use chrono::NaiveDate;
+use csv::ReaderBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-#[derive(Deserialize, Debug, Clone)]
-struct CustomerReturn {
+/// The column headers of Amazon's Customer Returns report, in order.
+const RETURNS_HEADERS: [&str; 13] = [
+    "return-date",
+    "order-id",
+    "sku",
+    "asin",
+    "fnsku",
+    "product-name",
+    "quantity",
+    "fulfillment-center-id",
+    "detailed-disposition",
+    "reason",
+    "status",
+    "license-plate-number",
+    "customer-comments",
+];
+/**
+A single row from Amazon's Customer Returns report.
+
+Fields are declared in the order of [`RETURNS_HEADERS`], so a serialized
+row lines up with the report's columns.
+*/
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CustomerReturn {
     #[serde(alias = "return-date")]
     return_date: String,
     #[serde(alias = "order-id")]
@@ -34,28 +61,107 @@ struct CustomerReturn {
 }
 impl CustomerReturn {
     fn from_csv_record(csv_record: csv::StringRecord) -> Result<Self, csv::Error> {
-        let hdr = vec![
-            "return-date",
-            "order-id",
-            "sku",
-            "asin",
-            "fnsku",
-            "product-name",
-            "quantity",
-            "fulfillment-center-id",
-            "detailed-disposition",
-            "reason",
-            "status",
-            "license-plate-number",
-            "customer-comments",
-        ];
-        let hdr_str = csv::StringRecord::from(hdr);
+        let hdr_str = csv::StringRecord::from(RETURNS_HEADERS.to_vec());
         csv_record.deserialize(Some(&hdr_str))
     }
+    /// Returns a reference to the return date of this [`CustomerReturn`].
+    pub fn get_return_date(&self) -> &str {
+        &self.return_date
+    }
+    /**
+    Attempts to parse the return date of this [`CustomerReturn`].
+
+    Both date-only and date-time forms are accepted, returning `None` if
+    the date cannot be parsed.
+    */
+    pub fn try_return_date(&self) -> Option<NaiveDate> {
+        parse_report_date(&self.return_date)
+    }
+    /// Returns a reference to the order id of this [`CustomerReturn`].
+    pub fn get_order_id(&self) -> &str {
+        &self.order_id
+    }
+    /// Returns a reference to the merchant SKU of this [`CustomerReturn`].
+    pub fn get_msku(&self) -> &str {
+        &self.msku
+    }
+    /// Returns a reference to the ASIN of this [`CustomerReturn`].
+    pub fn get_asin(&self) -> &str {
+        &self.asin
+    }
+    /// Returns a reference to the FNSKU of this [`CustomerReturn`].
+    pub fn get_fnsku(&self) -> &str {
+        &self.fnsku
+    }
+    /// Returns the unit quantity of this [`CustomerReturn`].
+    pub fn get_units(&self) -> u32 {
+        self.units
+    }
+    /// Returns a reference to the fulfillment center id of this [`CustomerReturn`].
+    pub fn get_fc_id(&self) -> &str {
+        &self.fc_id
+    }
+    /// Returns a reference to the detailed disposition of this [`CustomerReturn`].
+    pub fn get_disposition(&self) -> &str {
+        &self.disposition
+    }
+    /// Returns a reference to the return reason of this [`CustomerReturn`].
+    pub fn get_reason(&self) -> &str {
+        &self.reason
+    }
+    /**
+    Returns the reason of this [`CustomerReturn`] with surrounding and
+    repeated whitespace removed and uppercased, e.g. `" unwanted_item "`
+    becomes `"UNWANTED_ITEM"`.
+    */
+    fn normalized_reason(&self) -> String {
+        normalize(&self.reason)
+    }
+    /// Returns a reference to the status of this [`CustomerReturn`].
+    pub fn get_status(&self) -> &str {
+        &self.status
+    }
+    /**
+    Returns the status of this [`CustomerReturn`] normalized the same way as
+    the reason, e.g. `"Unit returned to inventory"` becomes
+    `"UNIT RETURNED TO INVENTORY"`.
+    */
+    fn normalized_status(&self) -> String {
+        normalize(&self.status)
+    }
+    /// Returns a reference to the license plate number of this [`CustomerReturn`].
+    pub fn get_lpn(&self) -> &str {
+        &self.lpn
+    }
+    /**
+    Returns the customer's comments on this [`CustomerReturn`] with
+    surrounding whitespace removed.
+
+    Returns `None` if there are no comments, or they are only whitespace.
+    */
+    pub fn try_comments(&self) -> Option<&str> {
+        let comments = self.customer_comments.as_deref()?.trim();
+        (!comments.is_empty()).then_some(comments)
+    }
+}
+/// Removes surrounding and repeated whitespace from `s`, then uppercases it.
+fn normalize(s: &str) -> String {
+    s.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_uppercase()
 }
-/// The iterator that is produced by the [`ReturnsBucket`] struct.
+/// The owning iterator that is produced by the [`ReturnsBucket`] struct.
 #[derive(Debug)]
-pub struct ReturnsBucketIter(CustomerReturn);
+pub struct ReturnsBucketIter(std::vec::IntoIter<CustomerReturn>);
+
+impl Iterator for ReturnsBucketIter {
+    type Item = CustomerReturn;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
 
 /**
 A container of customer return records.
@@ -64,17 +170,275 @@ Iterate over this struct to reach the contained data.
 */
 #[derive(Default, Debug)]
 pub struct ReturnsBucket {
-    vec: Vec<ReturnsBucketIter>,
+    vec: Vec<CustomerReturn>,
+}
+
+impl IntoIterator for ReturnsBucket {
+    type Item = CustomerReturn;
+    type IntoIter = ReturnsBucketIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ReturnsBucketIter(self.vec.into_iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a ReturnsBucket {
+    type Item = &'a CustomerReturn;
+    type IntoIter = std::slice::Iter<'a, CustomerReturn>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.iter()
+    }
 }
 
 impl ReturnsBucket {
     /// Creates a new [`ReturnsBucket`].
-    pub fn new(vec: Vec<ReturnsBucketIter>) -> Self {
+    pub fn new(vec: Vec<CustomerReturn>) -> Self {
         Self { vec }
     }
     /// Push an item onto the [`ReturnsBucket`].
-    fn push(&mut self, rb: ReturnsBucketIter) {
-        self.vec.push(rb)
+    fn push(&mut self, cr: CustomerReturn) {
+        self.vec.push(cr)
+    }
+    /**
+    Removes every [`CustomerReturn`] that shares its order id, LPN, FNSKU,
+    and units with an earlier one, keeping the first.
+
+    Amazon's rolling reports overlap at their boundaries, so consecutive
+    downloads will otherwise count the same return twice.
+    */
+    pub fn dedupe(&mut self) {
+        let mut seen = HashSet::new();
+        self.vec.retain(|cr| {
+            let key = (
+                cr.order_id.to_string(),
+                cr.lpn.to_string(),
+                cr.fnsku.to_string(),
+                cr.units,
+            );
+            seen.insert(key)
+        });
+    }
+    /// Moves every return of `other` onto the [`ReturnsBucket`], then dedupes.
+    pub fn merge(&mut self, mut other: ReturnsBucket) {
+        self.vec.append(&mut other.vec);
+        self.dedupe();
+    }
+    /**
+    Returns the sum of returned units across every [`CustomerReturn`].
+
+    The sum saturates at [`u32::MAX`] rather than overflowing.
+    */
+    pub fn total_units(&self) -> u32 {
+        self.vec
+            .iter()
+            .fold(0u32, |total, cr| total.saturating_add(cr.units))
+    }
+    /// Returns every distinct order id within the [`ReturnsBucket`].
+    pub fn unique_order_ids(&self) -> HashSet<&str> {
+        self.vec.iter().map(|cr| cr.get_order_id()).collect()
+    }
+    /**
+    Returns the sum of returned units for each FNSKU.
+
+    Rows with an empty FNSKU are grouped under the empty string key rather
+    than dropped, so the map always accounts for every returned unit. Sums
+    saturate at [`u32::MAX`].
+    */
+    pub fn units_by_fnsku(&self) -> HashMap<String, u32> {
+        let mut map = HashMap::new();
+        for cr in &self.vec {
+            let units = map.entry(cr.fnsku.to_string()).or_insert(0u32);
+            *units = units.saturating_add(cr.units);
+        }
+        map
+    }
+    /**
+    Returns a new [`ReturnsBucket`] holding clones of the returns whose
+    detailed disposition matches `disposition`, ignoring ASCII case.
+    */
+    pub fn filter_disposition(&self, disposition: &str) -> ReturnsBucket {
+        let vec = self
+            .vec
+            .iter()
+            .filter(|cr| cr.disposition.eq_ignore_ascii_case(disposition))
+            .cloned()
+            .collect::<Vec<CustomerReturn>>();
+        ReturnsBucket::new(vec)
+    }
+    /// Returns a new [`ReturnsBucket`] holding only the sellable returns.
+    pub fn sellable(&self) -> ReturnsBucket {
+        self.filter_disposition("SELLABLE")
+    }
+    /// Returns a new [`ReturnsBucket`] holding only the returns with customer comments.
+    pub fn with_comments(&self) -> ReturnsBucket {
+        let vec = self
+            .vec
+            .iter()
+            .filter(|cr| cr.try_comments().is_some())
+            .cloned()
+            .collect::<Vec<CustomerReturn>>();
+        ReturnsBucket::new(vec)
+    }
+    /**
+    Returns an iterator of `(order_id, comments)` over the returns with
+    customer comments, see [`CustomerReturn::try_comments`].
+    */
+    pub fn comments_iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vec
+            .iter()
+            .filter_map(|cr| Some((cr.get_order_id(), cr.try_comments()?)))
+    }
+    /**
+    Returns a new [`ReturnsBucket`] holding clones of the returns dated
+    between `start` and `end`, inclusive.
+
+    Returns with a date that cannot be parsed are excluded.
+    */
+    pub fn within(&self, start: NaiveDate, end: NaiveDate) -> ReturnsBucket {
+        let vec = self
+            .vec
+            .iter()
+            .filter(|cr| {
+                cr.try_return_date()
+                    .is_some_and(|date| start <= date && date <= end)
+            })
+            .cloned()
+            .collect::<Vec<CustomerReturn>>();
+        ReturnsBucket::new(vec)
+    }
+    /**
+    Partitions the returns into a [`ReturnsBucket`] per fulfillment center
+    id, e.g. to route restock decisions.
+
+    Every field of each return is kept, the buckets hold clones.
+    */
+    pub fn by_fulfillment_center(&self) -> HashMap<String, ReturnsBucket> {
+        let mut map = HashMap::new();
+        for cr in &self.vec {
+            map.entry(cr.fc_id.to_string())
+                .or_insert_with(ReturnsBucket::default)
+                .push(cr.clone());
+        }
+        map
+    }
+    /**
+    Maps each FNSKU to the `(msku, asin)` it was returned under, along with
+    the sorted FNSKUs that appear under more than one MSKU or ASIN.
+
+    The first mapping seen for a conflicting FNSKU is the one kept. Rows
+    with an empty FNSKU are skipped.
+    */
+    pub fn sku_crosswalk(&self) -> (HashMap<String, (String, String)>, Vec<String>) {
+        let mut map: HashMap<String, (String, String)> = HashMap::new();
+        let mut conflicts = HashSet::new();
+        for cr in self.vec.iter().filter(|cr| !cr.fnsku.is_empty()) {
+            let skus = map
+                .entry(cr.fnsku.to_string())
+                .or_insert_with(|| (cr.msku.to_string(), cr.asin.to_string()));
+            if skus.0 != cr.msku || skus.1 != cr.asin {
+                conflicts.insert(cr.fnsku.to_string());
+            };
+        }
+        let mut conflicts = conflicts.into_iter().collect::<Vec<String>>();
+        conflicts.sort_unstable();
+        (map, conflicts)
+    }
+    /**
+    Returns the number of returns for each reason.
+
+    Reasons are normalized by trimming whitespace and uppercasing. Counts
+    saturate at [`u32::MAX`].
+    */
+    pub fn reason_counts(&self) -> HashMap<String, u32> {
+        let mut map = HashMap::new();
+        for cr in &self.vec {
+            let count = map.entry(cr.normalized_reason()).or_insert(0u32);
+            *count = count.saturating_add(1);
+        }
+        map
+    }
+    /**
+    Returns the sum of returned units for each reason.
+
+    Reasons are normalized by trimming whitespace and uppercasing. Sums
+    saturate at [`u32::MAX`].
+    */
+    pub fn reason_units(&self) -> HashMap<String, u32> {
+        let mut map = HashMap::new();
+        for cr in &self.vec {
+            let units = map.entry(cr.normalized_reason()).or_insert(0u32);
+            *units = units.saturating_add(cr.units);
+        }
+        map
+    }
+    /**
+    Returns the sum of returned units for each status.
+
+    Statuses are normalized by trimming whitespace and uppercasing, e.g.
+    `"REIMBURSED"`. Sums saturate at [`u32::MAX`].
+    */
+    pub fn status_breakdown(&self) -> HashMap<String, u32> {
+        let mut map = HashMap::new();
+        for cr in &self.vec {
+            let units = map.entry(cr.normalized_status()).or_insert(0u32);
+            *units = units.saturating_add(cr.units);
+        }
+        map
+    }
+    /// Returns the sum of returned units that were reimbursed, see [`ReturnsBucket::status_breakdown`].
+    pub fn reimbursed_units(&self) -> u32 {
+        let breakdown = self.status_breakdown();
+        breakdown.get("REIMBURSED").copied().unwrap_or_default()
+    }
+    /**
+    Returns every [`CustomerReturn`] whose FNSKU does not appear within `plan`.
+
+    Useful for catching returns of items that were never expected inbound.
+    */
+    pub fn unexpected_returns<'a>(&'a self, plan: &Plan) -> Vec<&'a CustomerReturn> {
+        let fnskus = plan.fnsku_set();
+        self.vec
+            .iter()
+            .filter(|cr| !fnskus.contains(cr.get_fnsku()))
+            .collect()
+    }
+    /**
+    Returns every [`CustomerReturn`] whose order id appears nowhere within
+    `removals`.
+
+    These are returns made by customers, rather than units coming back from
+    a removal order.
+    */
+    pub fn orphan_returns<'a>(&'a self, removals: &RemovalReport) -> Vec<&'a CustomerReturn> {
+        let order_ids = removals
+            .into_iter()
+            .map(|removal| removal.get_order_id())
+            .collect::<HashSet<&str>>();
+        self.vec
+            .iter()
+            .filter(|cr| !order_ids.contains(cr.get_order_id()))
+            .collect()
+    }
+    /**
+    Writes every [`CustomerReturn`] to `w` as a Customer Returns report,
+    headed by [`RETURNS_HEADERS`].
+
+    The output can be read back with [`ReturnsBucket::from_csv_path`], e.g.
+    to save a filtered subset of a report.
+
+    # Errors
+
+    This function will return an error if writing to `w` fails.
+    */
+    pub fn to_csv<W: std::io::Write>(&self, w: W) -> csv::Result<()> {
+        let mut wtr = csv::WriterBuilder::new().has_headers(false).from_writer(w);
+        wtr.write_record(RETURNS_HEADERS)?;
+        for cr in &self.vec {
+            wtr.serialize(cr)?;
+        }
+        wtr.flush()?;
+        Ok(())
     }
     /**
     Creates a [`ReturnsBucket`] from a Customer Returns Csv.
@@ -91,12 +455,61 @@ impl ReturnsBucket {
     where
         P: AsRef<Path>,
     {
+        Self::from_csv_path_with_delimiter(path, b',')
+    }
+    /**
+    Creates a [`ReturnsBucket`] from a Customer Returns report using
+    `delimiter` to separate fields, e.g. `b'\t'` for Amazon's `.txt` reports.
+
+    # Errors
+
+    This function will error if it comes across any issue that may arise during
+    general IO / CSV reading. See [`csv::Error`] as any [`std::io::Error`] will
+    propagate through it.
+    */
+    pub fn from_csv_path_with_delimiter<P>(path: P, delimiter: u8) -> Result<Self, csv::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let rdr = ReaderBuilder::new().delimiter(delimiter).from_path(path)?;
+        Self::from_csv_reader(rdr)
+    }
+    /**
+    Creates a [`ReturnsBucket`] from the contents of a Customer Returns Csv,
+    such as an inline string or a report piped through stdin.
+
+    # Errors
+
+    This function will error if the CSV format is incorrect. See
+    [`csv::Error`].
+    */
+    pub fn from_csv_str(s: &str) -> Result<Self, csv::Error> {
+        Self::from_csv_reader(csv::Reader::from_reader(s.as_bytes()))
+    }
+    /**
+    Creates a [`ReturnsBucket`] from any reader of a Customer Returns report
+    using `delimiter` to separate fields, e.g. `b'\t'` for Amazon's `.txt`
+    reports.
+
+    # Errors
+
+    This function will error if reading fails or the CSV format is
+    incorrect. See [`csv::Error`].
+    */
+    pub fn from_reader_with_delimiter<R>(reader: R, delimiter: u8) -> Result<Self, csv::Error>
+    where
+        R: std::io::Read,
+    {
+        let rdr = ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(reader);
+        Self::from_csv_reader(rdr)
+    }
+    fn from_csv_reader<R: std::io::Read>(mut rdr: csv::Reader<R>) -> Result<Self, csv::Error> {
         let mut rb = ReturnsBucket::default();
-        let mut rdr = Reader::from_path(path)?;
         for row in rdr.records() {
             let cr = CustomerReturn::from_csv_record(row?)?;
-            let rbi = ReturnsBucketIter(cr);
-            rb.push(rbi);
+            rb.push(cr);
         }
         Ok(rb)
     }
@@ -105,6 +518,7 @@ impl ReturnsBucket {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use csv::Reader;
     static TEST_REMOVAL_SHIPMENT_RECORD: &str = "tests/data/CustomerReturns.csv";
 
     fn load_customer_return_csv_report() -> Vec<CustomerReturn> {
@@ -124,11 +538,214 @@ mod tests {
         assert!(!load_customer_return_csv_report().is_empty());
     }
     #[test]
+    fn returns_bucket_from_csv_str() {
+        let csv = "\
+return-date,order-id,sku,asin,fnsku,product-name,quantity,fulfillment-center-id,detailed-disposition,reason,status,license-plate-number,customer-comments
+2022-11-10,RMV-0001,MSKU-1,B000000001,X001AAAAA1,Widget One,6,ABE2,SELLABLE,NOT_AS_DESCRIBED,Unit returned to inventory,LPN0000001,
+";
+        let rb = ReturnsBucket::from_csv_str(csv).unwrap();
+        assert_eq!(rb.vec.len(), 1);
+        assert_eq!(rb.total_units(), 6);
+    }
+    #[test]
     fn create_returns_bucket() {
         let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD);
-        match rb {
-            Ok(_) => assert!(true),
-            Err(_) => assert!(false),
+        assert!(rb.is_ok());
+    }
+    #[test]
+    fn customer_return_accessors() {
+        let returns = load_customer_return_csv_report();
+        let first = returns.first().unwrap();
+        assert!(!first.get_fnsku().is_empty());
+        assert!(first.get_units() > 0);
+    }
+    #[test]
+    fn units_by_fnsku_accounts_for_every_unit() {
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let row_total: u32 = rb.vec.iter().map(|cr| cr.units).sum();
+        let map_total: u32 = rb.units_by_fnsku().values().sum();
+        assert_eq!(map_total, row_total);
+    }
+    #[test]
+    fn filter_sellable_returns() {
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let sellable = rb.sellable();
+        assert!(!sellable.vec.is_empty());
+        assert!(sellable.vec.len() < rb.vec.len());
+        assert!(sellable
+            .vec
+            .iter()
+            .all(|cr| cr.get_disposition().eq_ignore_ascii_case("SELLABLE")));
+    }
+    #[test]
+    fn group_by_fulfillment_center() {
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let by_fc = rb.by_fulfillment_center();
+        assert!(by_fc.len() > 1);
+        let grouped = by_fc.values().map(|bucket| bucket.vec.len()).sum::<usize>();
+        assert_eq!(grouped, rb.vec.len());
+        for (fc_id, bucket) in &by_fc {
+            assert!(bucket.vec.iter().all(|cr| cr.get_fc_id() == fc_id));
         }
     }
+    #[test]
+    fn sellable_to_csv_round_trip() {
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let sellable = rb.sellable();
+        let mut buf = Vec::new();
+        sellable.to_csv(&mut buf).unwrap();
+
+        let csv = String::from_utf8(buf).unwrap();
+        assert!(csv.starts_with(&RETURNS_HEADERS.join(",")));
+        let reloaded = ReturnsBucket::from_csv_str(&csv).unwrap();
+        assert_eq!(reloaded.vec.len(), sellable.vec.len());
+        assert_eq!(reloaded.total_units(), sellable.total_units());
+        assert_eq!(reloaded.unique_order_ids(), sellable.unique_order_ids());
+    }
+    #[test]
+    fn crosswalk_skus() {
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let (crosswalk, conflicts) = rb.sku_crosswalk();
+        assert!(conflicts.is_empty());
+        let expect = ("MSKU-1".to_string(), "B000000001".to_string());
+        assert_eq!(crosswalk.get("X001AAAAA1"), Some(&expect));
+
+        let csv = format!(
+            "{}\n{}\n{}\n",
+            RETURNS_HEADERS.join(","),
+            "2022-11-10,111-1,MSKU-1,B000000001,X001AAAAA1,Widget,1,ABE2,SELLABLE,DEFECTIVE,,LPN1,",
+            "2022-11-11,111-2,MSKU-1B,B000000001,X001AAAAA1,Widget,1,ABE2,SELLABLE,DEFECTIVE,,LPN2,",
+        );
+        let (crosswalk, conflicts) = ReturnsBucket::from_csv_str(&csv).unwrap().sku_crosswalk();
+        assert_eq!(conflicts, vec!["X001AAAAA1"]);
+        assert_eq!(crosswalk.get("X001AAAAA1"), Some(&expect));
+    }
+    #[test]
+    fn units_by_status() {
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let breakdown = rb.status_breakdown();
+        assert_eq!(breakdown.values().sum::<u32>(), rb.total_units());
+        assert_eq!(breakdown.get("UNIT RETURNED TO INVENTORY"), Some(&11));
+        assert_eq!(rb.reimbursed_units(), 2);
+        assert_eq!(ReturnsBucket::default().reimbursed_units(), 0);
+    }
+    #[test]
+    fn returns_with_comments() {
+        // One of the blank rows holds only whitespace
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let commented = rb.with_comments();
+        assert_eq!(commented.vec.len(), 2);
+        let comments = rb.comments_iter().collect::<Vec<(&str, &str)>>();
+        assert_eq!(
+            comments,
+            vec![
+                ("111-0000000-0000001", "Arrived broken"),
+                ("RMV-0002", "Did not work"),
+            ]
+        );
+    }
+    #[test]
+    fn parse_return_dates() {
+        let returns = load_customer_return_csv_report();
+        let mut cr = returns.first().unwrap().clone();
+        let expect = NaiveDate::from_ymd_opt(2022, 11, 10);
+        assert_eq!(cr.try_return_date(), expect);
+        cr.return_date = "2022-11-10".to_string();
+        assert_eq!(cr.try_return_date(), expect);
+        cr.return_date = "11/10/2022 maybe".to_string();
+        assert_eq!(cr.try_return_date(), None);
+    }
+    #[test]
+    fn returns_within_range() {
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let start = NaiveDate::from_ymd_opt(2022, 11, 11).unwrap();
+        let end = NaiveDate::from_ymd_opt(2022, 11, 14).unwrap();
+        let within = rb.within(start, end);
+        assert!(!within.vec.is_empty());
+        assert!(within.vec.len() < rb.vec.len());
+        for cr in &within {
+            let date = cr.try_return_date().unwrap();
+            assert!(start <= date && date <= end);
+        }
+    }
+    #[test]
+    fn load_tab_delimited_returns() {
+        let mut rdr = Reader::from_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let mut wtr = csv::WriterBuilder::new()
+            .delimiter(b'\t')
+            .from_writer(Vec::new());
+        wtr.write_record(rdr.headers().unwrap()).unwrap();
+        for row in rdr.records() {
+            wtr.write_record(&row.unwrap()).unwrap();
+        }
+        let tsv = wtr.into_inner().unwrap();
+
+        let rb = ReturnsBucket::from_reader_with_delimiter(tsv.as_slice(), b'\t').unwrap();
+        assert_eq!(rb.vec.len(), load_customer_return_csv_report().len());
+    }
+    #[test]
+    fn iterate_returns_bucket() {
+        let row_count = load_customer_return_csv_report().len();
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        assert_eq!((&rb).into_iter().count(), row_count);
+        let owned = rb.into_iter().collect::<Vec<CustomerReturn>>();
+        assert_eq!(owned.len(), row_count);
+    }
+    #[test]
+    fn total_units_and_unique_orders() {
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        assert_eq!(rb.total_units(), 13);
+        let orders = rb.unique_order_ids();
+        assert_eq!(orders.len(), 5);
+        assert!(orders.contains("RMV-0002"));
+        assert_eq!(ReturnsBucket::default().total_units(), 0);
+    }
+    #[test]
+    fn merge_overlapping_reports() {
+        let mut rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let single = rb.vec.len();
+        let again = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        rb.merge(again);
+        assert_eq!(rb.vec.len(), single);
+
+        let mut cr = rb.vec[0].clone();
+        cr.units += 1;
+        rb.push(cr);
+        rb.dedupe();
+        assert_eq!(rb.vec.len(), single + 1);
+    }
+    #[test]
+    fn reason_histograms() {
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let counts = rb.reason_counts();
+        assert_eq!(counts.values().sum::<u32>() as usize, rb.vec.len());
+        assert_eq!(counts.get("DEFECTIVE"), Some(&2));
+        assert_eq!(counts.get("UNWANTED_ITEM"), Some(&2));
+
+        let units = rb.reason_units();
+        let total = rb.vec.iter().map(|cr| cr.get_units()).sum::<u32>();
+        assert_eq!(units.values().sum::<u32>(), total);
+        assert_eq!(units.get("UNWANTED_ITEM"), Some(&5));
+    }
+    #[test]
+    fn flag_returns_without_a_removal() {
+        let removals = RemovalReport::from_csv_path("tests/data/RemovalShipments.csv").unwrap();
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let orphans = rb.orphan_returns(&removals);
+        assert_eq!(orphans.len(), 3);
+        let order_ids = orphans
+            .iter()
+            .map(|cr| cr.get_order_id())
+            .collect::<Vec<_>>();
+        assert!(order_ids.iter().all(|id| id.starts_with("111-")));
+        assert!(!order_ids.contains(&"RMV-0001"));
+    }
+    #[test]
+    fn flag_returns_missing_from_plan() {
+        let plan = Plan::from_csv_path("tests/data/STAPlan.csv").unwrap();
+        let rb = ReturnsBucket::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+        let unexpected = rb.unexpected_returns(&plan);
+        assert_eq!(unexpected.len(), 1);
+        assert_eq!(unexpected[0].get_fnsku(), "X009ZZZZZ9");
+    }
 }
@@ -1,33 +1,154 @@
 #![allow(dead_code)]
-use crate::sta::result::{ErrorKind, Result};
-use serde::{Deserialize, Serialize};
+use crate::sta::weight::{grams_to_lbs, WeightUnit, GRAMS_PER_POUND};
+use crate::sta::{ErrorKind, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Serialize, Clone, Copy)]
+/**
+Serializes a weight held in grams as pounds, matching the plan's columns.
+*/
+fn serialize_pounds<S>(grams: &u32, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_f64(grams_to_lbs(*grams))
+}
+
+/**
+Deserializes a whole number that may be formatted for display, such as
+`"1,200"` or `" 50 "`.
+
+Thousands separators and surrounding whitespace are removed before parsing,
+anything else that is not a number is still an error.
+*/
+fn deserialize_count<'de, D>(deserializer: D) -> std::result::Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(count) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    let count = count.trim().replace(',', "");
+    if count.is_empty() {
+        return Ok(None);
+    };
+    let count = count.parse::<u32>().map_err(serde::de::Error::custom)?;
+    Ok(Some(count))
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 struct Case {
+    #[serde(rename = "Case Length")]
     length: u32,
+    #[serde(rename = "Case Width")]
     width: u32,
+    #[serde(rename = "Case Height")]
     height: u32,
+    #[serde(rename = "Case Weight", serialize_with = "serialize_pounds")]
     gram_weight: u32,
 }
 impl Case {
-    fn from_sorted_dims(length: u32, width: u32, height: u32, weight: f32) -> Self {
+    fn from_sorted_dims(length: u32, width: u32, height: u32, gram_weight: u32) -> Self {
         Case {
             length,
             width,
             height,
-            gram_weight: weight as u32,
+            gram_weight,
         }
     }
+    /**
+    Creates a [`Case`] from dimensions in any order, sorting them so the
+    length is the longest side and the height the shortest.
+
+    # Errors
+
+    This function will return [`ErrorKind::MissingPackedDimensions`] if any
+    dimension is zero, or [`ErrorKind::MissingPackedWeight`] if `grams == 0`.
+    */
+    fn try_from_dims(l: u32, w: u32, h: u32, grams: u32) -> Result<Self> {
+        let mut dims = [l, w, h];
+        if dims.contains(&0) {
+            return Err(ErrorKind::MissingPackedDimensions);
+        };
+        if grams == 0 {
+            return Err(ErrorKind::MissingPackedWeight);
+        };
+        dims.sort_unstable();
+        Ok(Self::from_sorted_dims(dims[2], dims[1], dims[0], grams))
+    }
+    /**
+    Returns the cubic volume of the [`Case`].
+
+    Dimensions are assumed to be in inches, making this cubic inches.
+    */
+    fn volume(&self) -> u64 {
+        self.length as u64 * self.width as u64 * self.height as u64
+    }
+    /**
+    Returns the dimensional weight (in grams) of the [`Case`].
+
+    Dimensions are assumed to be in inches, so `divisor` is expected to be
+    a carrier's cubic inches per pound (139 being the most common). The
+    weight in pounds is converted to grams and rounded up. Returns `None` if
+    `divisor == 0`.
+    */
+    fn dim_weight_grams(&self, divisor: u32) -> Option<u32> {
+        if divisor == 0 {
+            return None;
+        };
+        let pounds = self.volume() as f64 / divisor as f64;
+        Some((pounds * GRAMS_PER_POUND).ceil() as u32)
+    }
+}
+/**
+A single physical carton of a [`Packed`] entry.
+
+Produced by [`Entry::cartons`], a carton always holds a single FNSKU.
+*/
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct Carton {
+    #[serde(rename = "FNSKU")]
+    fnsku: String,
+    #[serde(rename = "Quantity")]
+    units: u32,
+    #[serde(flatten)]
+    case: Case,
+}
+impl Carton {
+    /// Returns a reference to the FNSKU within this [`Carton`].
+    pub fn get_fnsku(&self) -> &str {
+        &self.fnsku
+    }
+    /// Returns the units within this [`Carton`].
+    pub fn get_units(&self) -> u32 {
+        self.units
+    }
+    /// Returns the length of this [`Carton`].
+    pub fn get_length(&self) -> u32 {
+        self.case.length
+    }
+    /// Returns the width of this [`Carton`].
+    pub fn get_width(&self) -> u32 {
+        self.case.width
+    }
+    /// Returns the height of this [`Carton`].
+    pub fn get_height(&self) -> u32 {
+        self.case.height
+    }
+    /// Returns the weight (in grams) of this [`Carton`].
+    pub fn get_gram_weight(&self) -> u32 {
+        self.case.gram_weight
+    }
 }
 /**
 A single "packed" record from a shipping plan.
 
 Packed represents __box(es)__ that will contain a single Sku.
 */
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Clone)]
 pub struct Packed {
     id: u32,
     fnsku: String,
+    msku: Option<String>,
     units: u32,
     per_case: u32,
     case: Case,
@@ -66,10 +187,11 @@ A single "loose" record from a shipping plan.
 
 Loose represents items that will likely be packed with different items.
 */
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Clone)]
 pub struct Loose {
     id: u32,
     fnsku: String,
+    msku: Option<String>,
     units: u32,
     gram_weight: u32,
     group: String,
@@ -103,6 +225,19 @@ impl EntryFormat for Loose {
         None
     }
 }
+/**
+A "bare" record from a shipping plan, holding only the id, FNSKU, and units.
+
+Bare entries are useful for quickly prototyping a plan before the packing
+details are known.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BareEntry {
+    id: u32,
+    fnsku: String,
+    msku: Option<String>,
+    units: u32,
+}
 trait EntryFormat {
     fn get_fnsku(&self) -> &str;
     fn get_units(&self) -> u32;
@@ -118,76 +253,519 @@ Various representations of a single row on a "shipping plan".
 
 * [`Entry::Loose`] represents an item destined for a mixed box.
 * [`Entry::Packed`] represent an item destined for a case packing
+* [`Entry::Bare`] represents an item that has not been given a pack type
+
+Serializes with the same column names as the plan, tagged by `Pack Type`,
+see [`Entry::with_unit`].
 */
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Clone)]
 pub enum Entry {
     Loose(Loose),
     Packed(Packed),
+    Bare(BareEntry),
+}
+impl Serialize for Entry {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.with_unit(WeightUnit::default()).serialize(serializer)
+    }
+}
+/**
+Serializes an [`Entry`] with every weight written as a [`WeightUnit`],
+rounded to two decimals.
+
+Created with [`Entry::with_unit`].
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct EntryWithUnit<'a> {
+    entry: &'a Entry,
+    unit: WeightUnit,
+}
+impl Serialize for EntryWithUnit<'_> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let weight = |grams| UnitWeight {
+            grams,
+            unit: self.unit,
+        };
+        let view = match self.entry {
+            Entry::Loose(l) => EntryView::Loose {
+                id: l.id,
+                fnsku: &l.fnsku,
+                msku: l.msku.as_deref(),
+                units: l.units,
+                weight: weight(l.gram_weight),
+                group: &l.group,
+            },
+            Entry::Packed(p) => EntryView::Packed {
+                id: p.id,
+                fnsku: &p.fnsku,
+                msku: p.msku.as_deref(),
+                units: p.units,
+                per_case: p.per_case,
+                length: p.case.length,
+                width: p.case.width,
+                height: p.case.height,
+                weight: weight(p.case.gram_weight),
+            },
+            Entry::Bare(b) => EntryView::Bare {
+                id: b.id,
+                fnsku: &b.fnsku,
+                msku: b.msku.as_deref(),
+                units: b.units,
+            },
+        };
+        view.serialize(serializer)
+    }
+}
+/// A weight held in grams, serialized as `unit`.
+#[derive(Debug, Clone, Copy)]
+struct UnitWeight {
+    grams: u32,
+    unit: WeightUnit,
+}
+impl Serialize for UnitWeight {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self.unit.convert_grams(self.grams))
+    }
+}
+/// The columns an [`Entry`] serializes to, tagged by `Pack Type`.
+#[derive(Serialize)]
+#[serde(tag = "Pack Type")]
+enum EntryView<'a> {
+    Loose {
+        #[serde(rename = "Info")]
+        id: u32,
+        #[serde(rename = "FNSKU")]
+        fnsku: &'a str,
+        #[serde(rename = "MSKU", skip_serializing_if = "Option::is_none")]
+        msku: Option<&'a str>,
+        #[serde(rename = "Quantity")]
+        units: u32,
+        #[serde(rename = "Unit Weight")]
+        weight: UnitWeight,
+        #[serde(rename = "Staging Group")]
+        group: &'a str,
+    },
+    Packed {
+        #[serde(rename = "Info")]
+        id: u32,
+        #[serde(rename = "FNSKU")]
+        fnsku: &'a str,
+        #[serde(rename = "MSKU", skip_serializing_if = "Option::is_none")]
+        msku: Option<&'a str>,
+        #[serde(rename = "Quantity")]
+        units: u32,
+        #[serde(rename = "Case QT")]
+        per_case: u32,
+        #[serde(rename = "Case Length")]
+        length: u32,
+        #[serde(rename = "Case Width")]
+        width: u32,
+        #[serde(rename = "Case Height")]
+        height: u32,
+        #[serde(rename = "Case Weight")]
+        weight: UnitWeight,
+    },
+    Bare {
+        #[serde(rename = "Info")]
+        id: u32,
+        #[serde(rename = "FNSKU")]
+        fnsku: &'a str,
+        #[serde(rename = "MSKU", skip_serializing_if = "Option::is_none")]
+        msku: Option<&'a str>,
+        #[serde(rename = "Quantity")]
+        units: u32,
+    },
 }
 impl Entry {
+    /// Returns the id (the `Info` column) of this [`Entry`].
+    pub fn get_id(&self) -> u32 {
+        match self {
+            Entry::Loose(l) => l.id,
+            Entry::Packed(p) => p.id,
+            Entry::Bare(b) => b.id,
+        }
+    }
+    /// Replaces the id (the `Info` column) of this [`Entry`].
+    pub(crate) fn set_id(&mut self, id: u32) {
+        match self {
+            Entry::Loose(l) => l.id = id,
+            Entry::Packed(p) => p.id = id,
+            Entry::Bare(b) => b.id = id,
+        }
+    }
     /// Returns a reference to the contained FNSKU string of this [`Entry`].
     pub fn get_fnsku(&self) -> &str {
         match self {
             Entry::Loose(l) => l.get_fnsku(),
             Entry::Packed(p) => p.get_fnsku(),
+            Entry::Bare(b) => &b.fnsku,
+        }
+    }
+    /// Returns a reference to the merchant SKU of this [`Entry`], if one was given.
+    pub fn try_msku(&self) -> Option<&str> {
+        match self {
+            Entry::Loose(l) => l.msku.as_deref(),
+            Entry::Packed(p) => p.msku.as_deref(),
+            Entry::Bare(b) => b.msku.as_deref(),
+        }
+    }
+    /// Returns the total unit quantity of this [`Entry`].
+    pub fn get_units(&self) -> u32 {
+        match self {
+            Entry::Loose(l) => l.get_units(),
+            Entry::Packed(p) => p.get_units(),
+            Entry::Bare(b) => b.units,
         }
     }
     /**
     Returns a reference to the contained group name string of this [`Entry`].
 
-    This function will always return `None` If the entry is [`Self::Packed`].
+    This function will always return `None` If the entry is not [`Self::Loose`].
     */
     pub fn try_group_name(&self) -> Option<&str> {
         match self {
             Entry::Loose(inner) => inner.try_group_name(),
             Entry::Packed(inner) => inner.try_group_name(),
+            Entry::Bare(_) => None,
         }
     }
     /**
     Returns the contained case length of the [`Entry`].
 
-    This function will always return `None` If the entry is [`Self::Loose`].
+    This function will always return `None` If the entry is not [`Self::Packed`].
     */
     pub fn try_case_length(&self) -> Option<u32> {
         Some(match self {
             Entry::Loose(inner) => inner.try_case_dimensions()?.length,
             Entry::Packed(inner) => inner.try_case_dimensions()?.length,
+            Entry::Bare(_) => return None,
         })
     }
     /**
     Returns the contained case width of the [`Entry`].
 
-    This function will always return `None` If the entry is [`Self::Loose`].
+    This function will always return `None` If the entry is not [`Self::Packed`].
     */
     pub fn try_case_width(&self) -> Option<u32> {
         Some(match self {
             Entry::Loose(inner) => inner.try_case_dimensions()?.width,
             Entry::Packed(inner) => inner.try_case_dimensions()?.width,
+            Entry::Bare(_) => return None,
         })
     }
     /**
     Returns the contained case height of the [`Entry`].
 
-    This function will always return `None` If the entry is [`Self::Loose`].
+    This function will always return `None` If the entry is not [`Self::Packed`].
     */
     pub fn try_case_height(&self) -> Option<u32> {
         Some(match self {
             Entry::Loose(inner) => inner.try_case_dimensions()?.height,
             Entry::Packed(inner) => inner.try_case_dimensions()?.height,
+            Entry::Bare(_) => return None,
         })
     }
     /**
+    Returns the contained case `(length, width, height)` of the [`Entry`].
+
+    Dimensions are always ordered from largest to smallest, regardless of
+    the order they were given in. This function will always return `None`
+    If the entry is not [`Self::Packed`].
+    */
+    pub fn try_case_dimensions(&self) -> Option<(u32, u32, u32)> {
+        let case = match self {
+            Entry::Loose(inner) => inner.try_case_dimensions()?,
+            Entry::Packed(inner) => inner.try_case_dimensions()?,
+            Entry::Bare(_) => return None,
+        };
+        Some((case.length, case.width, case.height))
+    }
+    /**
+    Returns the volume of the contained case of the [`Entry`].
+
+    Dimensions are assumed to be in inches, making this cubic inches. This
+    function will always return `None` If the entry is not [`Self::Packed`].
+    */
+    pub fn try_case_volume(&self) -> Option<u64> {
+        let case = match self {
+            Entry::Loose(inner) => inner.try_case_dimensions()?,
+            Entry::Packed(inner) => inner.try_case_dimensions()?,
+            Entry::Bare(_) => return None,
+        };
+        Some(case.volume())
+    }
+    /**
     Returns the contained case weight (in grams) of the [`Entry`].
 
-    This function will always return `None` If the entry is [`Self::Loose`].
+    This function will always return `None` If the entry is not [`Self::Packed`].
     */
     pub fn try_case_gram_weight(&self) -> Option<u32> {
         Some(match self {
             Entry::Loose(inner) => inner.try_case_dimensions()?.gram_weight,
             Entry::Packed(inner) => inner.try_case_dimensions()?.gram_weight,
+            Entry::Bare(_) => return None,
         })
     }
     /**
+    Returns the weight (in grams) of a single unit of the [`Entry`].
+
+    Packed entries divide the case weight by the units per case, rounding
+    down. Returns `None` if `units == 0`, or `per_case == 0` for packed
+    entries.
+    */
+    pub fn try_unit_gram_weight(&self) -> Option<u32> {
+        match self {
+            Entry::Loose(inner) if inner.units > 0 => Some(inner.gram_weight),
+            Entry::Packed(inner) if inner.units > 0 => {
+                inner.case.gram_weight.checked_div(inner.per_case)
+            }
+            _ => None,
+        }
+    }
+    /**
+    Returns the total weight (in grams) of the [`Entry`].
+
+    Packed entries weigh the number of cases times the case weight, loose
+    entries weigh the units times the unit weight.
+
+    Returns `None` if the entry is bare, the number of cases cannot be
+    computed, or the multiplication overflows.
+    */
+    pub fn try_total_gram_weight(&self) -> Option<u32> {
+        match self {
+            Entry::Loose(inner) => inner.units.checked_mul(inner.gram_weight),
+            Entry::Packed(inner) => self
+                .num_of_cases()
+                .ok()?
+                .checked_mul(inner.case.gram_weight),
+            Entry::Bare(_) => None,
+        }
+    }
+    /**
+    Returns the billable weight (in grams) of a single case of the
+    [`Entry`], the greater of the actual case weight and its dimensional
+    weight.
+
+    Case dimensions are assumed to be in inches, so `dim_divisor` is the
+    carrier's cubic inches per pound, e.g. 139 or 166. Returns `None` if the
+    entry is not packed, or if `dim_divisor == 0`.
+    */
+    pub fn billable_weight_grams(&self, dim_divisor: u32) -> Option<u32> {
+        let Entry::Packed(inner) = self else {
+            return None;
+        };
+        let dim_weight = inner.case.dim_weight_grams(dim_divisor)?;
+        Some(inner.case.gram_weight.max(dim_weight))
+    }
+    /**
+    Adds the units of `other` into this [`Entry`] if both describe the same
+    item packed the same way, returning `true` if they were combined.
+
+    Packed entries must share the FNSKU, units per case, and [`Case`]. Loose
+    entries must share the FNSKU, unit weight, and staging group. Bare entries
+    must only share the FNSKU. Entries of different pack types are never
+    combined, nor are entries whose combined units would overflow.
+    */
+    pub fn try_consolidate(&mut self, other: &Entry) -> bool {
+        let (units, other_units) = match (self, other) {
+            (Entry::Packed(p), Entry::Packed(o))
+                if p.fnsku == o.fnsku && p.per_case == o.per_case && p.case == o.case =>
+            {
+                (&mut p.units, o.units)
+            }
+            (Entry::Loose(l), Entry::Loose(o))
+                if l.fnsku == o.fnsku && l.gram_weight == o.gram_weight && l.group == o.group =>
+            {
+                (&mut l.units, o.units)
+            }
+            (Entry::Bare(b), Entry::Bare(o)) if b.fnsku == o.fnsku => (&mut b.units, o.units),
+            _ => return false,
+        };
+        let Some(total) = units.checked_add(other_units) else {
+            return false;
+        };
+        *units = total;
+        true
+    }
+    /**
+    Sets the units of the [`Entry`].
+
+    # Errors
+
+    This function will return [`ErrorKind::NonDivisibleCaseQt`] if the entry
+    is packed and `units` do not fill its cases evenly, leaving the units
+    unchanged.
+    */
+    pub fn set_units(&mut self, units: u32) -> Result<()> {
+        match self {
+            Entry::Packed(p) if !units.is_multiple_of(p.per_case) => {
+                return Err(ErrorKind::NonDivisibleCaseQt);
+            }
+            Entry::Packed(p) => p.units = units,
+            Entry::Loose(l) => l.units = units,
+            Entry::Bare(b) => b.units = units,
+        };
+        Ok(())
+    }
+    /**
+    Expands the [`Entry`] into one [`Carton`] per case.
+
+    Loose and bare entries have no cartons of their own, returning an empty
+    `Vec`.
+
+    # Errors
+
+    This function will return an error if the number of cases cannot be
+    computed, see [`Entry::num_of_cases`].
+    */
+    pub fn cartons(&self) -> Result<Vec<Carton>> {
+        let Entry::Packed(inner) = self else {
+            return Ok(Vec::new());
+        };
+        let carton = Carton {
+            fnsku: inner.fnsku.to_string(),
+            units: inner.per_case,
+            case: inner.case,
+        };
+        Ok(vec![carton; self.num_of_cases()? as usize])
+    }
+    /**
+    Splits the [`Entry`] into one [`Entry`] per case.
+
+    Each packed case becomes a copy of the entry holding only `per_case`
+    units. Loose and bare entries have no cases and are returned whole.
+
+    # Errors
+
+    This function will return an error if the number of cases cannot be
+    computed, see [`Entry::num_of_cases`].
+    */
+    pub fn expand_cases(&self) -> Result<Vec<Entry>> {
+        let Entry::Packed(inner) = self else {
+            return Ok(vec![self.clone()]);
+        };
+        let case = Entry::Packed(Packed {
+            units: inner.per_case,
+            ..inner.clone()
+        });
+        Ok(vec![case; self.num_of_cases()? as usize])
+    }
+    /**
+    Re-cases a packed [`Entry`] whose case weighs more than
+    `max_case_grams` into lighter cases of the same dimensions.
+
+    Weight is assumed to be spread evenly between the units of a case, so
+    the case weight is scaled in proportion to the new units per case,
+    rounding up, but never over `max_case_grams` nor under a single gram.
+    Units that do not fill a whole case are split off into a second entry,
+    with the same id, holding a single smaller case; the plan gives it a
+    fresh id when rebalancing. A unit that is heavier than
+    `max_case_grams` on its own is cased alone, keeping its own weight.
+
+    Loose and bare entries, and cases within the limit, are returned whole.
+
+    # Errors
+
+    This function will return an error if the number of cases cannot be
+    computed, see [`Entry::num_of_cases`].
+    */
+    pub fn split_heavy_cases(&self, max_case_grams: u32) -> Result<Vec<Entry>> {
+        self.num_of_cases()?;
+        let Entry::Packed(inner) = self else {
+            return Ok(vec![self.clone()]);
+        };
+        if inner.case.gram_weight <= max_case_grams {
+            return Ok(vec![self.clone()]);
+        };
+        let fits = max_case_grams as u64 * inner.per_case as u64 / inner.case.gram_weight as u64;
+        let recase = |per_case: u32| {
+            let grams =
+                (inner.case.gram_weight as u64 * per_case as u64).div_ceil(inner.per_case as u64);
+            let grams = match fits {
+                0 => grams,
+                _ => grams.min(max_case_grams as u64),
+            };
+            Packed {
+                per_case,
+                case: Case {
+                    gram_weight: (grams as u32).max(1),
+                    ..inner.case
+                },
+                ..inner.clone()
+            }
+        };
+        let per_case = (fits as u32).max(1);
+        let remainder = inner.units % per_case;
+
+        let mut entries = Vec::new();
+        if inner.units >= per_case {
+            let full = Packed {
+                units: inner.units - remainder,
+                ..recase(per_case)
+            };
+            entries.push(Entry::Packed(full));
+        };
+        if remainder > 0 {
+            let rest = Packed {
+                units: remainder,
+                ..recase(remainder)
+            };
+            entries.push(Entry::Packed(rest));
+        };
+        Ok(entries)
+    }
+    /**
+    Materializes the [`Entry`] into one [`BareEntry`] per case.
+
+    `n` is counted in cases, not units: each packed case becomes a
+    [`BareEntry`] holding `per_case` units. Loose and bare entries have no
+    cases and materialize into a single [`BareEntry`] holding every unit.
+
+    # Errors
+
+    This function will return an error if the number of cases cannot be
+    computed, see [`Entry::num_of_cases`].
+    */
+    pub fn materialize(&self) -> Result<Vec<BareEntry>> {
+        let Entry::Packed(inner) = self else {
+            return Ok(vec![self.to_bare()]);
+        };
+        let case = BareEntry {
+            units: inner.per_case,
+            ..self.to_bare()
+        };
+        Ok(vec![case; self.num_of_cases()? as usize])
+    }
+    /**
+    Converts the [`Entry`] into a [`BareEntry`], keeping only the id, FNSKU,
+    MSKU, and units.
+
+    Case and staging group details are discarded, so shipments can be
+    compared on quantity alone.
+    */
+    pub fn to_bare(&self) -> BareEntry {
+        let (id, fnsku, msku, units) = match self {
+            Entry::Loose(l) => (l.id, &l.fnsku, &l.msku, l.units),
+            Entry::Packed(p) => (p.id, &p.fnsku, &p.msku, p.units),
+            Entry::Bare(b) => (b.id, &b.fnsku, &b.msku, b.units),
+        };
+        BareEntry {
+            id,
+            fnsku: fnsku.to_string(),
+            msku: msku.clone(),
+            units,
+        }
+    }
+    /**
     Attemps to build an [`Entry`] from a single CSV record.
 
     # Errors
@@ -196,7 +774,90 @@ impl Entry {
     deserialized into an Entry.
     */
     pub fn from_csv_record(str_rec: csv::StringRecord) -> Result<Self> {
-        EntryParser::from_string_record(str_rec)?.build()
+        Self::from_csv_record_with_unit(str_rec, WeightUnit::default())
+    }
+    /**
+    Attemps to build an [`Entry`] from a single CSV record, reading every
+    weight as `unit`.
+
+    # Errors
+
+    This function will return an error if the record is unable to be
+    deserialized into an Entry.
+    */
+    pub fn from_csv_record_with_unit(str_rec: csv::StringRecord, unit: WeightUnit) -> Result<Self> {
+        EntryParser::from_string_record(str_rec)?.build_owned_with_unit(unit)
+    }
+    /**
+    Converts the [`Entry`] back into a CSV record, ordered by [`PLAN_HEADERS`]
+    and followed by the MSKU, see [`MSKU_HEADER`].
+
+    Weights are converted from grams back into pounds, rounded to two
+    decimals. Columns that do not apply to the pack type are left blank.
+    */
+    pub fn to_string_record(&self) -> csv::StringRecord {
+        self.to_string_record_with_unit(WeightUnit::default())
+    }
+    /**
+    Converts the [`Entry`] back into a CSV record, writing every weight as
+    `unit`, see [`Entry::to_string_record`].
+
+    Weights are rounded to two decimals.
+    */
+    pub fn to_string_record_with_unit(&self, unit: WeightUnit) -> csv::StringRecord {
+        let to_unit = |grams: u32| format!("{:.2}", unit.convert_grams(grams));
+        let record = match self {
+            Entry::Loose(l) => vec![
+                l.id.to_string(),
+                l.fnsku.to_string(),
+                l.units.to_string(),
+                "Loose".to_string(),
+                l.group.to_string(),
+                to_unit(l.gram_weight),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ],
+            Entry::Packed(p) => vec![
+                p.id.to_string(),
+                p.fnsku.to_string(),
+                p.units.to_string(),
+                "Packed".to_string(),
+                String::new(),
+                String::new(),
+                p.per_case.to_string(),
+                p.case.length.to_string(),
+                p.case.width.to_string(),
+                p.case.height.to_string(),
+                to_unit(p.case.gram_weight),
+                self.num_of_cases()
+                    .map(|cases| cases.to_string())
+                    .unwrap_or_default(),
+            ],
+            Entry::Bare(b) => {
+                let mut record = vec![
+                    b.id.to_string(),
+                    b.fnsku.to_string(),
+                    b.units.to_string(),
+                    "Bare".to_string(),
+                ];
+                record.resize(PLAN_HEADERS.len(), String::new());
+                record
+            }
+        };
+        let mut record = csv::StringRecord::from(record);
+        record.push_field(self.try_msku().unwrap_or_default());
+        record
+    }
+    /**
+    Returns a view of the [`Entry`] that serializes with every weight
+    written as `unit` rather than pounds, see [`EntryWithUnit`].
+    */
+    pub fn with_unit(&self, unit: WeightUnit) -> EntryWithUnit<'_> {
+        EntryWithUnit { entry: self, unit }
     }
     /**
     Returns the num of cases of this [`Entry`].
@@ -209,10 +870,10 @@ impl Entry {
     * `per_case == 0`.
 
     */
-    fn num_of_cases(&self) -> Result<u32> {
+    pub fn num_of_cases(&self) -> Result<u32> {
         // Destructure if Packed
         let Entry::Packed(packed_entry) = self else {
-            // Return 1 if Loose
+            // Return 1 if Loose or Bare
             return Ok(1);
         };
         if !is_evenly_packed(packed_entry) {
@@ -237,8 +898,88 @@ impl Entry {
     pub fn is_loose(&self) -> bool {
         matches!(self, Entry::Loose(_))
     }
+    /**
+    Returns `true` if the [`Entry`] is [`BareEntry`].
+    */
+    pub fn is_bare(&self) -> bool {
+        matches!(self, Entry::Bare(_))
+    }
+    /**
+    Returns the [`EntryKind`] of the [`Entry`], so the pack type can be
+    matched on or grouped by without borrowing the payload.
+    */
+    pub fn kind(&self) -> EntryKind {
+        match self {
+            Entry::Loose(_) => EntryKind::Loose,
+            Entry::Packed(_) => EntryKind::Packed,
+            Entry::Bare(_) => EntryKind::Bare,
+        }
+    }
 }
 
+/// The variant of an [`Entry`], without its payload, see [`Entry::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    Packed,
+    Loose,
+    Bare,
+}
+
+/**
+The pack type of a row within a shipping plan.
+
+Parsing ignores case and surrounding whitespace, and accepts the common
+synonyms used across sheets, e.g. `"cp"` or `"case packed"` for
+[`PackConfig::Packed`].
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PackConfig {
+    Packed,
+    Loose,
+    Bare,
+}
+impl std::str::FromStr for PackConfig {
+    type Err = ErrorKind;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "packed" | "case packed" | "cp" => Ok(PackConfig::Packed),
+            "loose" | "individual" | "lp" => Ok(PackConfig::Loose),
+            "bare" => Ok(PackConfig::Bare),
+            _ => Err(ErrorKind::InvalidPackType),
+        }
+    }
+}
+impl TryFrom<csv::StringRecord> for Entry {
+    type Error = ErrorKind;
+
+    /// Attempts to build an [`Entry`] from a CSV record, see [`Entry::from_csv_record`].
+    fn try_from(record: csv::StringRecord) -> Result<Self> {
+        Entry::from_csv_record(record)
+    }
+}
+impl std::fmt::Display for Entry {
+    /// Formats the [`Entry`] as a short, human readable line, e.g.
+    /// `X001ABCDE2 x50 (5 cases of 10)` or `X001ABCDE2 x12 loose [GroupA]`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} x{}", self.get_fnsku(), self.get_units())?;
+        match self {
+            Entry::Loose(l) => write!(f, " loose [{}]", l.group),
+            Entry::Packed(p) => match self.num_of_cases() {
+                Ok(cases) => write!(f, " ({} of {})", count_cases(cases), p.per_case),
+                Err(_) => write!(f, " (uneven cases of {})", p.per_case),
+            },
+            Entry::Bare(_) => Ok(()),
+        }
+    }
+}
+/// Formats `count` as a number of cases, e.g. `1 case` or `2 cases`.
+pub(crate) fn count_cases(count: u32) -> String {
+    match count {
+        1 => "1 case".to_string(),
+        _ => format!("{count} cases"),
+    }
+}
 /// Returns `true` if `p.units / p.per_case` has a remainder `== 0`.
 fn is_evenly_packed(p: &Packed) -> bool {
     let units = &p.units;
@@ -247,6 +988,27 @@ fn is_evenly_packed(p: &Packed) -> bool {
         // check the unit quantity is evenly divisible by case quantity
         .is_some_and(|remainder| remainder == 0)
 }
+/// The column headers of a CSV shipping plan, in order.
+pub(crate) const PLAN_HEADERS: [&str; 12] = [
+    "Info",
+    "FNSKU",
+    "Quantity",
+    "Pack Type",
+    "Staging Group",
+    "Unit Weight",
+    "Case QT",
+    "Case Length",
+    "Case Width",
+    "Case Height",
+    "Case Weight",
+    "Total Cases",
+];
+/**
+The optional column that follows [`PLAN_HEADERS`], holding the merchant SKU.
+
+Sheets may end at `Total Cases`, but an exported plan always includes it.
+*/
+pub(crate) const MSKU_HEADER: &str = "MSKU";
 /**
 A helper for dealing with input strings, mostly CSV shipping plans.
 
@@ -256,14 +1018,18 @@ Holds parsing and deserialization logic for reading in csv plan records.
 use [`EntryParser::from_string_record`] to load a [`csv::StringRecord`],
 then call [`EntryParser::build`] to build.
 */
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct EntryParser {
     #[serde(alias = "Info")]
     id: Option<u32>,
     #[serde(alias = "FNSKU")]
     fnsku: Option<String>,
+    #[serde(alias = "MSKU")]
+    #[serde(alias = "Merchant SKU")]
+    msku: Option<String>,
     #[serde(alias = "Quantity")]
     #[serde(alias = "Total Quantity")]
+    #[serde(default, deserialize_with = "deserialize_count")]
     units: Option<u32>,
     #[serde(alias = "Pack Type")]
     pack_type: Option<String>,
@@ -272,6 +1038,7 @@ pub struct EntryParser {
     #[serde(alias = "Unit Weight")]
     unit_weight: Option<f32>,
     #[serde(alias = "Case QT")]
+    #[serde(default, deserialize_with = "deserialize_count")]
     case_qt: Option<u32>,
     #[serde(alias = "Case Length")]
     case_length: Option<f32>,
@@ -282,48 +1049,133 @@ pub struct EntryParser {
     #[serde(alias = "Case Weight")]
     case_weight: Option<f32>,
     #[serde(alias = "Total Cases")]
+    #[serde(default, deserialize_with = "deserialize_count")]
     total_cases: Option<u32>,
 }
 
 impl EntryParser {
-    /// Attempt to build an [`Entry`] from an [`EntryParser`]
+    /// Attempt to build an [`Entry`] from an [`EntryParser`] with weights in pounds.
     fn build(&self) -> Result<Entry> {
+        self.build_with_unit(WeightUnit::default())
+    }
+    /**
+    Attempt to build an [`Entry`] from an [`EntryParser`], reading every
+    weight as `unit`.
+    */
+    fn build_with_unit(&self, unit: WeightUnit) -> Result<Entry> {
         // Check if Bare entry can be created
         self.check_bare_validity()?;
 
+        match self.pack_config()? {
+            PackConfig::Packed => Ok(Entry::Packed(self.build_packed(unit)?)),
+            PackConfig::Loose => Ok(Entry::Loose(self.build_loose(unit)?)),
+            PackConfig::Bare => Ok(Entry::Bare(self.build_bare()?)),
+        }
+    }
+    /**
+    Consumes the [`EntryParser`], building an [`Entry`] with weights in pounds.
+
+    Unlike [`EntryParser::build`], the FNSKU, merchant SKU, and staging group
+    strings are moved into the [`Entry`] rather than cloned.
+    */
+    fn build_owned(self) -> Result<Entry> {
+        self.build_owned_with_unit(WeightUnit::default())
+    }
+    /**
+    Consumes the [`EntryParser`], building an [`Entry`] while reading every
+    weight as `unit`, see [`EntryParser::build_owned`].
+
+    Every field is validated before any string is moved out.
+    */
+    fn build_owned_with_unit(self, unit: WeightUnit) -> Result<Entry> {
+        self.check_bare_validity()?;
+
+        match self.pack_config()? {
+            PackConfig::Packed => Ok(Entry::Packed(self.into_packed(unit)?)),
+            PackConfig::Loose => Ok(Entry::Loose(self.into_loose(unit)?)),
+            PackConfig::Bare => Ok(Entry::Bare(self.into_bare()?)),
+        }
+    }
+    /// Returns the [`PackConfig`] named by the pack type, bare if it is blank.
+    fn pack_config(&self) -> Result<PackConfig> {
         match &self.pack_type {
-            Some(pt) if pt == "Packed" => Ok(Entry::Packed(self.build_packed()?)),
-            Some(pt) if pt == "Loose" => Ok(Entry::Loose(self.build_loose()?)),
-            _ => Err(ErrorKind::InvalidPackType)?,
+            Some(pt) => pt.parse::<PackConfig>(),
+            None => Ok(PackConfig::Bare),
         }
     }
     /**
+    Build a [`Entry::Bare`] from the [`EntryParser`]
+
+    Only the id, FNSKU, and units are required, every other field is ignored.
+    */
+    fn build_bare(&self) -> Result<BareEntry> {
+        self.clone().into_bare()
+    }
+    /**
+    Consumes the [`EntryParser`], building a [`BareEntry`], see
+    [`EntryParser::build_bare`].
+    */
+    fn into_bare(self) -> Result<BareEntry> {
+        self.check_bare_validity()?;
+
+        Ok(BareEntry {
+            id: self.id.unwrap(),
+            fnsku: self.fnsku.unwrap(),
+            msku: self.msku,
+            units: self.units.unwrap(),
+        })
+    }
+    /**
     Build a [`Entry::Loose`] from the [`EntryParser`]
 
     Passing an Entry without [`Loose`] fields will cause the build
     to fail, returning a [`ErrorKind::MissingGroup`], or
     [`ErrorKind::MissingUnitWeight`] Error.
     */
-    fn build_loose(&self) -> Result<Loose> {
+    fn build_loose(&self, unit: WeightUnit) -> Result<Loose> {
+        self.clone().into_loose(unit)
+    }
+    /**
+    Consumes the [`EntryParser`], building a [`Loose`], see
+    [`EntryParser::build_loose`].
+
+    Every field is validated before any string is moved out.
+    */
+    fn into_loose(self, unit: WeightUnit) -> Result<Loose> {
         // Check if the bare information is there
         self.check_bare_validity()?;
 
-        let fnsku = self.fnsku.as_ref().unwrap();
-        let group = self.staging_group.as_ref().unwrap();
-        let weight = self.unit_weight.unwrap();
-
-        // this converts the weight into grams, rounding up after conversion
-        // entirely due to the fact that I would prefer to work with u32
-        let gram_weight = (weight * 453.6).ceil() as u32;
+        let gram_weight = self.check_loose(unit)?;
 
         Ok(Loose {
             id: self.id.unwrap(),
-            fnsku: fnsku.to_string(),
+            fnsku: self.fnsku.unwrap(),
+            msku: self.msku,
             units: self.units.unwrap(),
             gram_weight,
-            group: group.to_string(),
+            group: self.staging_group.unwrap(),
         })
     }
+    /**
+    Checks the [`Loose`] fields of the [`EntryParser`], returning the unit
+    weight in grams.
+
+    The staging group is known to be present once this returns `Ok`.
+    */
+    fn check_loose(&self, unit: WeightUnit) -> Result<u32> {
+        self.staging_group.as_ref().ok_or(ErrorKind::MissingGroup)?;
+        let weight = self.unit_weight.ok_or(ErrorKind::MissingUnitWeight)?;
+
+        // this converts the weight into grams, entirely due to the fact that
+        // I would prefer to work with u32
+        let gram_weight = unit.to_grams(weight);
+
+        // Zero, negative, and weights too small to reach a gram all become 0
+        if gram_weight == 0 {
+            return Err(ErrorKind::MissingUnitWeight);
+        };
+        Ok(gram_weight)
+    }
     // Checks to see if essential components are present to build the entry
     fn check_bare_validity(&self) -> Result<()> {
         let Some(_id) =  self.id else {
@@ -332,9 +1184,6 @@ impl EntryParser {
         let Some(_fnsku) = &self.fnsku else {
             return Err(ErrorKind::MissingFnsku)
         };
-        let Some(_pack_type) = &self.pack_type else {
-            return Err(ErrorKind::MissingPackType)
-        };
         let Some(_units) = self.units else {
             return Err(ErrorKind::MissingUnits)
         };
@@ -347,10 +1196,38 @@ impl EntryParser {
     to fail, returning a [`ErrorKind::MissingPackedWeight`], or
     [`ErrorKind::MissingPackedDimensions`] Error.
     */
-    fn build_packed(&self) -> Result<Packed> {
+    fn build_packed(&self, unit: WeightUnit) -> Result<Packed> {
+        self.clone().into_packed(unit)
+    }
+    /**
+    Consumes the [`EntryParser`], building a [`Packed`], see
+    [`EntryParser::build_packed`].
+
+    Every field is validated before any string is moved out.
+    */
+    fn into_packed(self, unit: WeightUnit) -> Result<Packed> {
         // Check if the bare information is there
         self.check_bare_validity()?;
 
+        let case = self.check_packed(unit)?;
+
+        Ok(Packed {
+            id: self.id.unwrap(),
+            fnsku: self.fnsku.unwrap(),
+            msku: self.msku,
+            units: self.units.unwrap(),
+            per_case: self.case_qt.unwrap(),
+            case,
+        })
+    }
+    /**
+    Checks the [`Packed`] fields of the [`EntryParser`], returning the
+    [`Case`].
+
+    The bare fields must already be known to be present, see
+    [`EntryParser::check_bare_validity`].
+    */
+    fn check_packed(&self, unit: WeightUnit) -> Result<Case> {
         // Check if the CaseQt is missing
         let Some(case_qt) = self.case_qt else {
             return Err(ErrorKind::MissingCaseQt)
@@ -359,70 +1236,132 @@ impl EntryParser {
             return Err(ErrorKind::MissingCaseQt);
         };
 
-        // Div the total units by the CaseQt
-        self.units
-            // This is fine to do since it will just cause the following method
-            // to return None anyways.
-            .unwrap_or(0)
-            .checked_rem(case_qt)
-            .ok_or(ErrorKind::NonDivisibleCaseQt)?;
+        // Check the total units fill every case evenly, the units are
+        // known to be present after checking the bare validity
+        if !self.units.unwrap().is_multiple_of(case_qt) {
+            return Err(ErrorKind::NonDivisibleCaseQt);
+        };
 
-        let weight = self.case_weight.ok_or(ErrorKind::MissingPackedWeight)?;
+        // A declared total cases is optional, but must agree when present
+        let computed = self.units.unwrap() / case_qt;
+        if let Some(declared) = self.total_cases {
+            if declared != computed {
+                return Err(ErrorKind::TotalCasesMismatch { declared, computed });
+            }
+        };
 
-        // Create a vec from the dimensions for iteration
-        let dims = vec![self.case_length, self.case_height, self.case_weight];
+        let weight = self.case_weight.ok_or(ErrorKind::MissingPackedWeight)?;
 
-        // Look if all dimensions are Some() > 0
-        if !dims.iter().all(|dim| dim.is_some_and(|dim| dim > 0.0)) {
+        // Look if all dimensions are present
+        let [Some(length), Some(width), Some(height)] =
+            [self.case_length, self.case_width, self.case_height]
+        else {
             return Err(ErrorKind::MissingPackedDimensions);
         };
 
-        let dims_ref = &mut dims
+        // Round each dimension up, negative values saturate to zero
+        Case::try_from_dims(
+            length.ceil() as u32,
+            width.ceil() as u32,
+            height.ceil() as u32,
+            unit.to_grams(weight),
+        )
+    }
+    pub fn from_string_record(str_rec: csv::StringRecord) -> Result<EntryParser> {
+        let mut binding = csv::StringRecord::from(PLAN_HEADERS.to_vec());
+        binding.push_field(MSKU_HEADER);
+        Self::deserialize_record(&str_rec, &binding)
+    }
+    /**
+    Deserializes `record` into an [`EntryParser`], matching fields by the
+    column names within `headers`.
+
+    A field that fails to deserialize, such as a non-numeric `Quantity`, is
+    reported as an [`ErrorKind::InvalidField`] naming the column and value.
+    */
+    fn deserialize_record(record: &csv::StringRecord, headers: &csv::StringRecord) -> Result<Self> {
+        record.deserialize::<Self>(Some(headers)).map_err(|error| {
+            let csv::ErrorKind::Deserialize { err, .. } = error.kind() else {
+                return ErrorKind::from(error);
+            };
+            // Errors raised by custom deserializers do not carry the field
+            let index = err
+                .field()
+                .map(|index| index as usize)
+                .or_else(|| Self::find_invalid_field(record, headers));
+            let column = index.and_then(|index| headers.get(index));
+            let value = index.and_then(|index| record.get(index));
+            match (column, value) {
+                (Some(column), Some(value)) => ErrorKind::InvalidField {
+                    column: column.to_string(),
+                    value: value.to_string(),
+                },
+                _ => ErrorKind::from(error),
+            }
+        })
+    }
+    /**
+    Returns the index of the first field of `record` that fails to
+    deserialize on its own.
+    */
+    fn find_invalid_field(
+        record: &csv::StringRecord,
+        headers: &csv::StringRecord,
+    ) -> Option<usize> {
+        headers
             .iter()
-            // Unwrap each dimension, round up, cast to u32
-            .map(|x| x.unwrap().ceil() as u32)
-            .collect::<Vec<u32>>();
+            .zip(record.iter())
+            .position(|(column, value)| {
+                let column = csv::StringRecord::from(vec![column]);
+                let value = csv::StringRecord::from(vec![value]);
+                value.deserialize::<Self>(Some(&column)).is_err()
+            })
+    }
+    /**
+    Builds every [`Entry`] from a CSV with a header row.
 
-        dims_ref.sort_unstable();
+    Unlike [`EntryParser::from_string_record`], columns are matched by the
+    names in the header row, so their order does not matter and unknown
+    columns are ignored.
 
-        let case = Case::from_sorted_dims(
-            // length
-            dims_ref.pop().unwrap(),
-            // width
-            dims_ref.pop().unwrap(),
-            // height
-            dims_ref.pop().unwrap(),
-            weight * 453.6,
-        );
+    # Errors
 
-        let fnsku = self.fnsku.as_ref().unwrap();
+    This function will return an error for the first row that cannot be
+    read or built into an [`Entry`].
+    */
+    pub fn from_reader_with_headers<R: std::io::Read>(reader: R) -> Result<Vec<Entry>> {
+        Self::entries_from_reader(reader).collect()
+    }
+    /**
+    Lazily builds each [`Entry`] from a CSV with a header row, one record at
+    a time.
 
-        Ok(Packed {
-            id: self.id.unwrap(),
-            fnsku: fnsku.to_string(),
-            units: self.units.unwrap(),
-            per_case: self.case_qt.unwrap(),
-            case,
+    Nothing is read until the iterator is advanced, so very large plans can
+    be processed without holding every entry in memory. Columns are matched
+    by the names in the header row, as with
+    [`EntryParser::from_reader_with_headers`].
+    */
+    pub fn entries_from_reader<R: std::io::Read>(reader: R) -> impl Iterator<Item = Result<Entry>> {
+        let mut rdr = csv::Reader::from_reader(reader);
+        let mut headers: Option<csv::StringRecord> = None;
+        let mut record = csv::StringRecord::new();
+        std::iter::from_fn(move || {
+            let headers = match headers {
+                Some(ref headers) => headers,
+                None => match rdr.headers() {
+                    Ok(read) => headers.insert(read.clone()),
+                    Err(error) => return Some(Err(error.into())),
+                },
+            };
+            match rdr.read_record(&mut record) {
+                Ok(true) => {
+                    Some(Self::deserialize_record(&record, headers).and_then(Self::build_owned))
+                }
+                Ok(false) => None,
+                Err(error) => Some(Err(error.into())),
+            }
         })
     }
-    pub fn from_string_record(str_rec: csv::StringRecord) -> Result<EntryParser> {
-        let binding = csv::StringRecord::from(vec![
-            "Info",
-            "FNSKU",
-            "Quantity",
-            "Pack Type",
-            "Staging Group",
-            "Unit Weight",
-            "Case QT",
-            "Case Length",
-            "Case Width",
-            "Case Height",
-            "Case Weight",
-            "Total Cases",
-        ]);
-        let hdr = Some(&binding);
-        Ok(str_rec.deserialize::<Self>(hdr)?)
-    }
 }
 #[allow(unused_must_use)]
 #[cfg(test)]
@@ -452,4 +1391,551 @@ mod tests {
             .collect();
         assert_eq!(expect, results);
     }
+    fn parser_from_row(row: &str) -> EntryParser {
+        let record = csv::StringRecord::from(row.split(',').collect::<Vec<_>>());
+        EntryParser::from_string_record(record).unwrap()
+    }
+    #[test]
+    fn packed_case_sorts_all_three_dimensions() {
+        let parser = parser_from_row("1,X001ABCDE2,50,Packed,,,10,4,10,2,20,5");
+        let packed = parser.build_packed(WeightUnit::Pounds).unwrap();
+        assert_eq!(packed.case.length, 10);
+        assert_eq!(packed.case.width, 4);
+        assert_eq!(packed.case.height, 2);
+    }
+    #[test]
+    fn case_dimensions_are_largest_first() {
+        let packed = parser_from_row("1,X001ABCDE2,50,Packed,,,10,4,10,2,20,5").build();
+        assert_eq!(packed.unwrap().try_case_dimensions(), Some((10, 4, 2)));
+        let loose = parser_from_row("2,X001ABCDE3,1,Loose,GroupA,1.0,,,,,,").build();
+        assert_eq!(loose.unwrap().try_case_dimensions(), None);
+    }
+    #[test]
+    fn case_volume_of_entries() {
+        let packed = parser_from_row("1,X001ABCDE2,50,Packed,,,10,4,10,2,20,5").build();
+        assert_eq!(packed.unwrap().try_case_volume(), Some(80));
+        let loose = parser_from_row("2,X001ABCDE3,1,Loose,GroupA,1.0,,,,,,").build();
+        assert_eq!(loose.unwrap().try_case_volume(), None);
+    }
+    #[test]
+    fn packed_missing_height_is_missing_dimensions() {
+        let parser = parser_from_row("1,X001ABCDE2,50,Packed,,,10,10,4,,20,5");
+        let result = parser.build_packed(WeightUnit::Pounds);
+        assert!(matches!(result, Err(ErrorKind::MissingPackedDimensions)));
+    }
+    #[test]
+    fn case_from_dims_rejects_zero() {
+        let case = Case::try_from_dims(8, 12, 10, 1000).unwrap();
+        assert_eq!(case, Case::from_sorted_dims(12, 10, 8, 1000));
+        assert!(matches!(
+            Case::try_from_dims(12, 10, 0, 1000),
+            Err(ErrorKind::MissingPackedDimensions)
+        ));
+        assert!(matches!(
+            Case::try_from_dims(12, 10, 8, 0),
+            Err(ErrorKind::MissingPackedWeight)
+        ));
+    }
+    #[test]
+    fn packed_zero_height_or_weight() {
+        let zero_height = parser_from_row("1,X001ABCDE2,50,Packed,,,10,12,10,0,20,");
+        assert!(matches!(
+            zero_height.build(),
+            Err(ErrorKind::MissingPackedDimensions)
+        ));
+        let zero_weight = parser_from_row("1,X001ABCDE2,50,Packed,,,10,12,10,8,0,");
+        assert!(matches!(
+            zero_weight.build(),
+            Err(ErrorKind::MissingPackedWeight)
+        ));
+    }
+    #[test]
+    fn loose_zero_unit_weight() {
+        for weight in ["0.0", "0", "-1.5"] {
+            let row = format!("1,X001ABCDE2,12,Loose,GroupA,{weight},,,,,,");
+            assert!(matches!(
+                parser_from_row(&row).build(),
+                Err(ErrorKind::MissingUnitWeight)
+            ));
+        }
+        let positive = parser_from_row("1,X001ABCDE2,12,Loose,GroupA,0.5,,,,,,");
+        assert_eq!(positive.build().unwrap().try_unit_gram_weight(), Some(227));
+    }
+    #[test]
+    fn set_units_checks_packed_divisibility() {
+        let mut packed = parser_from_row("1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5")
+            .build()
+            .unwrap();
+        packed.set_units(30).unwrap();
+        assert_eq!(packed.num_of_cases().unwrap(), 3);
+        assert!(matches!(
+            packed.set_units(45),
+            Err(ErrorKind::NonDivisibleCaseQt)
+        ));
+        assert_eq!(packed.get_units(), 30);
+
+        let mut loose = parser_from_row("2,X001ABCDE3,12,Loose,GroupA,0.5,,,,,,")
+            .build()
+            .unwrap();
+        loose.set_units(7).unwrap();
+        assert_eq!(loose.get_units(), 7);
+    }
+    #[test]
+    fn packed_non_divisible_units() {
+        let parser = parser_from_row("1,X001ABCDE2,7,Packed,,,2,12,10,8,20,");
+        let result = parser.build_packed(WeightUnit::Pounds);
+        assert!(matches!(result, Err(ErrorKind::NonDivisibleCaseQt)));
+    }
+    #[test]
+    fn packed_total_cases_mismatch() {
+        let parser = parser_from_row("1,X001ABCDE2,50,Packed,,,10,4,10,2,20,6");
+        let err = parser.build_packed(WeightUnit::Pounds).unwrap_err();
+        assert!(matches!(
+            err,
+            ErrorKind::TotalCasesMismatch {
+                declared: 6,
+                computed: 5
+            }
+        ));
+        let blank = parser_from_row("1,X001ABCDE2,50,Packed,,,10,4,10,2,20,");
+        assert!(blank.build_packed(WeightUnit::Pounds).is_ok());
+    }
+    #[test]
+    fn malformed_field_names_the_column() {
+        let row = "1,X001ABCDE2,N/A,Packed,,,10,12,10,8,20,5";
+        let record = csv::StringRecord::from(row.split(',').collect::<Vec<_>>());
+        let error = EntryParser::from_string_record(record).unwrap_err();
+        assert!(matches!(
+            &error,
+            ErrorKind::InvalidField { column, value } if column == "Quantity" && value == "N/A"
+        ));
+
+        let data = "FNSKU,Info,Quantity\nX001ABCDE2,one,5\n";
+        let error = EntryParser::from_reader_with_headers(data.as_bytes()).unwrap_err();
+        assert!(matches!(error, ErrorKind::InvalidField { column, .. } if column == "Info"));
+    }
+    #[test]
+    fn counts_allow_separators_and_whitespace() {
+        let data = "Info,FNSKU,Quantity,Case QT,Total Cases\n\
+                    1,X001ABCDE2,\"1,200\", 50 ,24\n\
+                    2,X001ABCDE3,abc,,\n";
+        let mut rdr = csv::Reader::from_reader(data.as_bytes());
+        let headers = rdr.headers().unwrap().clone();
+        let mut records = rdr.records().map(|record| record.unwrap());
+
+        let parser = EntryParser::deserialize_record(&records.next().unwrap(), &headers);
+        let parser = parser.unwrap();
+        assert_eq!(parser.units, Some(1200));
+        assert_eq!(parser.case_qt, Some(50));
+        assert_eq!(parser.total_cases, Some(24));
+
+        let error = EntryParser::deserialize_record(&records.next().unwrap(), &headers);
+        let column = match error {
+            Err(ErrorKind::InvalidField { column, .. }) => column,
+            _ => panic!("expected an invalid field"),
+        };
+        assert_eq!(column, "Quantity");
+    }
+    #[test]
+    fn malformed_csv_keeps_csv_error_source() {
+        use std::error::Error;
+        let data = "Info,FNSKU,Quantity\n1,X001ABCDE2\n";
+        let error = EntryParser::from_reader_with_headers(data.as_bytes()).unwrap_err();
+        assert!(matches!(error, ErrorKind::CsvError(_)));
+        assert!(error.source().is_some());
+    }
+    #[test]
+    fn total_gram_weight_of_uneven_packed_is_none() {
+        let case = Case::from_sorted_dims(12, 10, 8, 1000);
+        let entry = Entry::Packed(Packed {
+            id: 1,
+            fnsku: "X001ABCDE2".to_string(),
+            msku: None,
+            units: 7,
+            per_case: 2,
+            case,
+        });
+        assert_eq!(entry.try_total_gram_weight(), None);
+    }
+    #[test]
+    fn expand_uneven_packed_is_err() {
+        let case = Case::from_sorted_dims(12, 10, 8, 1000);
+        let entry = Entry::Packed(Packed {
+            id: 1,
+            fnsku: "X001ABCDE2".to_string(),
+            msku: None,
+            units: 7,
+            per_case: 2,
+            case,
+        });
+        assert!(matches!(
+            entry.expand_cases(),
+            Err(ErrorKind::NonDivisibleCaseQt)
+        ));
+    }
+    #[test]
+    fn materialize_one_bare_entry_per_case() -> Result<()> {
+        let case = Case::from_sorted_dims(12, 10, 8, 1000);
+        let packed = Entry::Packed(Packed {
+            id: 1,
+            fnsku: "X001ABCDE2".to_string(),
+            msku: None,
+            units: 50,
+            per_case: 10,
+            case,
+        });
+        let bare = packed.materialize()?;
+        assert_eq!(bare.len(), 5);
+        assert!(bare
+            .iter()
+            .all(|b| b.fnsku == "X001ABCDE2" && b.units == 10));
+
+        let loose = Entry::Loose(Loose {
+            id: 2,
+            fnsku: "X001ABCDE3".to_string(),
+            msku: None,
+            units: 12,
+            gram_weight: 100,
+            group: "GroupA".to_string(),
+        });
+        let bare = loose.materialize()?;
+        assert_eq!(bare.len(), 1);
+        assert_eq!(bare[0].units, 12);
+        Ok(())
+    }
+    #[test]
+    fn packed_and_loose_to_bare_are_equal() {
+        let case = Case::from_sorted_dims(12, 10, 8, 1000);
+        let packed = Entry::Packed(Packed {
+            id: 1,
+            fnsku: "X001ABCDE2".to_string(),
+            msku: None,
+            units: 50,
+            per_case: 10,
+            case,
+        });
+        let loose = Entry::Loose(Loose {
+            id: 1,
+            fnsku: "X001ABCDE2".to_string(),
+            msku: None,
+            units: 50,
+            gram_weight: 100,
+            group: "GroupA".to_string(),
+        });
+        assert_eq!(packed.to_bare(), loose.to_bare());
+        assert_eq!(packed.to_bare().units, 50);
+    }
+    #[test]
+    fn total_gram_weight_overflow_is_none() {
+        // 100,000 units at 50 kg each is far beyond u32::MAX grams
+        let loose = Entry::Loose(Loose {
+            id: 1,
+            fnsku: "X001ABCDE2".to_string(),
+            msku: None,
+            units: 100_000,
+            gram_weight: 50_000,
+            group: "GroupA".to_string(),
+        });
+        assert_eq!(loose.try_total_gram_weight(), None);
+
+        let case = Case::from_sorted_dims(12, 10, 8, u32::MAX / 2);
+        let packed = Entry::Packed(Packed {
+            id: 2,
+            fnsku: "X001ABCDE3".to_string(),
+            msku: None,
+            units: 3,
+            per_case: 1,
+            case,
+        });
+        assert_eq!(packed.try_total_gram_weight(), None);
+    }
+    #[test]
+    fn case_volume_and_dim_weight() {
+        let case = Case::from_sorted_dims(12, 10, 8, 1000);
+        assert_eq!(case.volume(), 960);
+        // 960 / 139 = 6.906.. lbs
+        assert_eq!(case.dim_weight_grams(139), Some(3133));
+        assert_eq!(case.dim_weight_grams(0), None);
+    }
+    #[test]
+    fn billable_weight() {
+        // 24x18x18 weighing 5 lb is bound by its 55.9 lb dimensional weight
+        let bulky = parser_from_row("1,X001ABCDE2,10,Packed,,,10,24,18,18,5,");
+        let bulky = bulky.build().unwrap();
+        let dim_weight = Case::from_sorted_dims(24, 18, 18, 0)
+            .dim_weight_grams(139)
+            .unwrap();
+        assert_eq!(bulky.billable_weight_grams(139), Some(dim_weight));
+        assert!(bulky.billable_weight_grams(166) < Some(dim_weight));
+
+        // 12x10x8 weighing 40 lb is bound by its actual weight
+        let dense = parser_from_row("2,X001ABCDE3,10,Packed,,,10,12,10,8,40,");
+        let dense = dense.build().unwrap();
+        assert_eq!(
+            dense.billable_weight_grams(139),
+            dense.try_case_gram_weight()
+        );
+
+        let loose = parser_from_row("3,X001ABCDE4,1,Loose,GroupA,1.0,,,,,,");
+        assert_eq!(loose.build().unwrap().billable_weight_grams(139), None);
+        assert_eq!(dense.billable_weight_grams(0), None);
+    }
+    #[test]
+    fn export_weights_in_unit() {
+        let loose = Entry::Loose(Loose {
+            id: 1,
+            fnsku: "X001ABCDE2".to_string(),
+            msku: None,
+            units: 3,
+            gram_weight: 454,
+            group: "GroupA".to_string(),
+        });
+        assert_eq!(&loose.to_string_record()[5], "1.00");
+        let grams = loose.to_string_record_with_unit(WeightUnit::Grams);
+        assert_eq!(&grams[5], "454.00");
+
+        let json = serde_json::to_value(loose.with_unit(WeightUnit::Pounds)).unwrap();
+        assert_eq!(json["Unit Weight"], 1.0);
+        let json = serde_json::to_value(loose.with_unit(WeightUnit::Grams)).unwrap();
+        assert_eq!(json["Unit Weight"], 454.0);
+        assert_eq!(json["FNSKU"], "X001ABCDE2");
+
+        let case = Case::from_sorted_dims(12, 10, 8, 1000);
+        let packed = Entry::Packed(Packed {
+            id: 2,
+            fnsku: "X001ABCDE3".to_string(),
+            msku: None,
+            units: 10,
+            per_case: 5,
+            case,
+        });
+        let grams = packed.to_string_record_with_unit(WeightUnit::Grams);
+        assert_eq!(&grams[10], "1000.00");
+        let json = serde_json::to_value(packed.with_unit(WeightUnit::Grams)).unwrap();
+        assert_eq!(json["Case Weight"], 1000.0);
+    }
+    #[test]
+    fn loose_and_packed_pounds_match() {
+        let loose = parser_from_row("1,X001ABCDE2,1,Loose,GroupA,1.0,,,,,,");
+        let packed = parser_from_row("2,X001ABCDE3,1,Packed,,,1,12,10,8,1.0,1");
+        let loose = loose.build_loose(WeightUnit::Pounds).unwrap();
+        let packed = packed.build_packed(WeightUnit::Pounds).unwrap();
+        assert_eq!(loose.gram_weight, packed.case.gram_weight);
+    }
+    #[test]
+    fn unit_gram_weight() {
+        let case = Case::from_sorted_dims(12, 10, 8, 1000);
+        let mut packed = Packed {
+            id: 1,
+            fnsku: "X001ABCDE2".to_string(),
+            msku: None,
+            units: 40,
+            per_case: 4,
+            case,
+        };
+        let entry = Entry::Packed(packed.clone());
+        assert_eq!(entry.try_unit_gram_weight(), Some(250));
+        packed.per_case = 0;
+        assert_eq!(Entry::Packed(packed.clone()).try_unit_gram_weight(), None);
+        packed.per_case = 4;
+        packed.units = 0;
+        assert_eq!(Entry::Packed(packed).try_unit_gram_weight(), None);
+
+        let loose = Loose {
+            id: 2,
+            fnsku: "X001ABCDE3".to_string(),
+            msku: None,
+            units: 0,
+            gram_weight: 227,
+            group: "GroupA".to_string(),
+        };
+        assert_eq!(Entry::Loose(loose).try_unit_gram_weight(), None);
+    }
+    #[test]
+    fn serialize_with_plan_columns() {
+        let row = "1,X00AAAAAA1,10,Packed,,,5,12,10,8,2.35,";
+        let packed = parser_from_row(row).build().unwrap();
+        let value = serde_json::to_value(&packed).unwrap();
+        assert_eq!(value["Pack Type"], "Packed");
+        assert_eq!(value["FNSKU"], "X00AAAAAA1");
+        assert_eq!(value["Case Length"], 12);
+        assert_eq!(value["Case Weight"], 2.35);
+
+        let row = "2,X00AAAAAA2,3,Loose,G1,0.5,,,,,,";
+        let loose = parser_from_row(row).build().unwrap();
+        let value = serde_json::to_value(&loose).unwrap();
+        assert_eq!(value["Pack Type"], "Loose");
+        assert_eq!(value["Staging Group"], "G1");
+        assert_eq!(value["Unit Weight"], 0.5);
+        assert!(value.get("Case Length").is_none());
+    }
+    #[test]
+    fn build_bare_entry() {
+        let record = csv::StringRecord::from(vec!["7", "X00AAAAAA7", "12"]);
+        let headers = csv::StringRecord::from(vec!["Info", "FNSKU", "Quantity"]);
+        let parser = record.deserialize::<EntryParser>(Some(&headers)).unwrap();
+        let bare = parser.build().unwrap();
+        assert!(bare.is_bare());
+        assert_eq!(bare.get_fnsku(), "X00AAAAAA7");
+        assert_eq!(bare.get_units(), 12);
+        assert_eq!(bare.num_of_cases().unwrap(), 1);
+        assert!(bare.try_total_gram_weight().is_none());
+        assert!(bare.cartons().unwrap().is_empty());
+
+        let labelled = parser_from_row("7,X00AAAAAA7,12,Bare,,,,,,,,");
+        assert!(labelled.build().unwrap().is_bare());
+        let missing = parser_from_row("7,X00AAAAAA7,,Bare,,,,,,,,").build();
+        assert!(matches!(missing, Err(ErrorKind::MissingUnits)));
+    }
+    #[test]
+    fn msku_survives_build() {
+        let data = "Info,FNSKU,MSKU,Quantity,Pack Type,Staging Group,Unit Weight\n\
+                    1,X00AAAAAA1,WIDGET-01,3,Loose,G1,0.5\n";
+        let mut rdr = csv::Reader::from_reader(data.as_bytes());
+        let parser = rdr.deserialize::<EntryParser>().next().unwrap().unwrap();
+        let entry = parser.build().unwrap();
+        assert_eq!(entry.try_msku(), Some("WIDGET-01"));
+
+        let without = parser_from_row("1,X00AAAAAA1,3,Loose,G1,0.5,,,,,,");
+        assert_eq!(without.build().unwrap().try_msku(), None);
+    }
+    #[test]
+    fn build_owned_matches_build() {
+        let row = "2,X00AAAAAA2,3,Loose,G1,0.5,,,,,,";
+        let borrowed = parser_from_row(row).build().unwrap();
+        // build_owned consumes the parser, so it cannot be built twice
+        let owned = parser_from_row(row).build_owned().unwrap();
+        assert_eq!(owned.get_fnsku(), "X00AAAAAA2");
+        assert_eq!(owned.try_group_name(), Some("G1"));
+        assert_eq!(owned.to_string_record(), borrowed.to_string_record());
+
+        let missing = parser_from_row("2,,3,Loose,G1,0.5,,,,,,").build_owned();
+        assert!(matches!(missing, Err(ErrorKind::MissingFnsku)));
+    }
+    #[test]
+    fn shuffled_columns_build_the_same_entries() {
+        let data = "Notes,Pack Type,Case Weight,Quantity,FNSKU,Case QT,Info,Staging Group,\
+                    Unit Weight,Case Length,Case Width,Case Height\n\
+                    n/a,Packed,20,50,X001AAAAA1,10,1,,,12,10,8\n\
+                    ,Loose,,12,X001AAAAA2,,2,GroupA,0.5,,,\n";
+        let shuffled = EntryParser::from_reader_with_headers(data.as_bytes()).unwrap();
+        let expected = [
+            "1,X001AAAAA1,50,Packed,,,10,12,10,8,20,",
+            "2,X001AAAAA2,12,Loose,GroupA,0.5,,,,,,",
+        ];
+        assert_eq!(shuffled.len(), expected.len());
+        for (entry, row) in shuffled.iter().zip(expected) {
+            let built = parser_from_row(row).build().unwrap();
+            assert_eq!(entry.to_string_record(), built.to_string_record());
+        }
+    }
+    #[test]
+    fn headers_skip_utf8_bom() {
+        let file = std::fs::File::open("tests/data/STAPlanBom.csv").unwrap();
+        let entries = EntryParser::from_reader_with_headers(file).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].to_string_record().get(0), Some("1"));
+    }
+    #[test]
+    fn entries_from_reader_builds_on_demand() {
+        // The final row is short a column, it must never be built
+        let csv = format!(
+            "{}\n{}\n{}\n{}\n{}\n",
+            PLAN_HEADERS.join(","),
+            "1,X001ABCDE1,12,Loose,GroupA,0.5,,,,,,",
+            "2,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            "3,X001ABCDE3,12,Loose,GroupA,0.5,,,,,,",
+            "4,X001ABCDE4,12,Loose,GroupA,0.5,,,,,",
+        );
+        let mut entries = EntryParser::entries_from_reader(csv.as_bytes());
+        let first = entries.by_ref().take(3).collect::<Result<Vec<Entry>>>();
+        let ids = first
+            .unwrap()
+            .iter()
+            .map(|e| e.get_id())
+            .collect::<Vec<u32>>();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert!(matches!(entries.next(), Some(Err(ErrorKind::CsvError(_)))));
+        assert!(entries.next().is_none());
+
+        let shuffled = "FNSKU,Pack Type,Quantity,Staging Group,Unit Weight,MSKU,Info\n\
+                        X001ABCDE1,Loose,12,GroupA,0.5,WIDGET-01,7\n";
+        let entry = EntryParser::entries_from_reader(shuffled.as_bytes())
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.get_id(), 7);
+        assert_eq!(entry.try_msku(), Some("WIDGET-01"));
+    }
+    #[test]
+    fn entry_kind_matches_variant() {
+        let packed = parser_from_row("1,X001ABCDE1,50,Packed,,,10,12,10,8,20,5");
+        let loose = parser_from_row("2,X001ABCDE2,12,Loose,GroupA,0.5,,,,,,");
+        let bare = parser_from_row("3,X001ABCDE3,7,,,,,,,,,");
+        assert_eq!(packed.build().unwrap().kind(), EntryKind::Packed);
+        assert_eq!(loose.build().unwrap().kind(), EntryKind::Loose);
+        assert_eq!(bare.build().unwrap().kind(), EntryKind::Bare);
+    }
+    #[test]
+    fn pack_type_casing_and_synonyms() {
+        for pack_type in ["packed", "PACKED", "Case Packed", "cp"] {
+            let row = format!("1,X00AAAAAA1,10,{pack_type},,,5,12,10,8,2,");
+            assert!(parser_from_row(&row).build().unwrap().is_packed());
+        }
+        for pack_type in ["LOOSE", " loose ", "Individual", "LP"] {
+            let row = format!("2,X00AAAAAA2,3,{pack_type},G1,0.5,,,,,,");
+            assert!(parser_from_row(&row).build().unwrap().is_loose());
+        }
+        let unknown = parser_from_row("3,X00AAAAAA3,3,Pallet,G1,0.5,,,,,,").build();
+        assert!(matches!(unknown, Err(ErrorKind::InvalidPackType)));
+    }
+    #[test]
+    fn display_entries() {
+        let packed = parser_from_row("1,X001ABCDE2,50,Packed,,,10,4,10,2,20,5").build();
+        let packed = packed.unwrap().to_string();
+        assert_eq!(packed, "X001ABCDE2 x50 (5 cases of 10)");
+        let loose = parser_from_row("2,X001ABCDE2,12,Loose,GroupA,1.0,,,,,,").build();
+        let loose = loose.unwrap().to_string();
+        assert_eq!(loose, "X001ABCDE2 x12 loose [GroupA]");
+        let bare = parser_from_row("3,X001ABCDE2,12,Bare,,,,,,,,").build();
+        assert_eq!(bare.unwrap().to_string(), "X001ABCDE2 x12");
+    }
+    #[test]
+    fn try_from_string_record() -> Result<()> {
+        let mut rdr = csv::Reader::from_path(TEST_PLAN)?;
+        let record = rdr.records().next().unwrap()?;
+        let entry: Entry = record.try_into()?;
+        assert_eq!(entry.get_fnsku(), "X001AAAAA1");
+        assert_eq!(entry.get_units(), 50);
+        Ok(())
+    }
+    #[test]
+    fn entry_ids() {
+        let packed = parser_from_row("4,X001ABCDE2,50,Packed,,,10,4,10,2,20,5").build();
+        assert_eq!(packed.unwrap().get_id(), 4);
+        let bare = parser_from_row("9,X001ABCDE2,12,,,,,,,,,").build();
+        assert_eq!(bare.unwrap().get_id(), 9);
+    }
+    #[test]
+    fn packed_accessors() {
+        let parser = parser_from_row("1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5");
+        let entry = parser.build().unwrap();
+        assert_eq!(entry.get_fnsku(), "X001ABCDE2");
+        assert_eq!(entry.try_case_length(), Some(12));
+        assert_eq!(entry.try_case_width(), Some(10));
+        assert_eq!(entry.try_case_height(), Some(8));
+        assert!(entry.try_case_gram_weight().is_some());
+        assert_eq!(entry.try_group_name(), None);
+    }
+    #[test]
+    fn loose_accessors() {
+        let parser = parser_from_row("2,X001ABCDE3,12,Loose,GroupA,0.5,,,,,,");
+        let entry = parser.build().unwrap();
+        assert_eq!(entry.get_fnsku(), "X001ABCDE3");
+        assert_eq!(entry.try_case_length(), None);
+        assert_eq!(entry.try_case_width(), None);
+        assert_eq!(entry.try_case_height(), None);
+        assert_eq!(entry.try_case_gram_weight(), None);
+        assert_eq!(entry.try_group_name(), Some("GroupA"));
+    }
 }
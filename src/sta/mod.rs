@@ -1,5 +1,9 @@
 pub mod entry;
 pub mod plan;
+pub mod validation;
+pub mod weight;
+
+pub use result::{BuildError, ErrorKind, Result, RowError};
 
 mod result {
     use thiserror::Error;
@@ -9,8 +13,12 @@ mod result {
         MissingId,
         #[error("Row is missing an Fnsku")]
         MissingFnsku,
-        #[error("Row is missing the PackType")]
-        MissingPackType,
+        #[error("No entry holds the FNSKU {fnsku}")]
+        FnskuNotFound { fnsku: String },
+        #[error("{count} entries hold the FNSKU {fnsku}, expected one")]
+        AmbiguousFnsku { fnsku: String, count: usize },
+        #[error("Entry {fnsku} is missing an MSKU")]
+        MissingMsku { fnsku: String },
         #[error("Row is missing the unit quantity")]
         MissingUnits,
         #[error("Row is declared as packed with dimensions missing")]
@@ -24,20 +32,68 @@ mod result {
         NonDivisibleCaseQt,
         #[error("Row is declared as packed with CaseQt missing")]
         MissingCaseQt,
+        #[error("Row declares {declared} total cases, but the units fill {computed}")]
+        TotalCasesMismatch { declared: u32, computed: u32 },
         #[error("A PackType is included, but cannot be recognized")]
         InvalidPackType,
         #[error("Row is declared as Loose with StagingGroup missing")]
         MissingGroup,
         #[error("Row is declared as Loose with UnitWeight missing")]
         MissingUnitWeight,
-        #[error("Unable to deserialized StringRecord")]
-        CsvError,
+        #[error("Column {column} holds an invalid value: {value:?}")]
+        InvalidField { column: String, value: String },
+        #[error("Unable to deserialize StringRecord: {0}")]
+        CsvError(#[from] csv::Error),
+        #[error("Unable to open the CSV: {0}")]
+        Io(#[from] std::io::Error),
     }
     pub type Result<T> = std::result::Result<T, ErrorKind>;
 
-    impl From<csv::Error> for ErrorKind {
-        fn from(_value: csv::Error) -> Self {
-            Self::CsvError
+    /// An [`ErrorKind`] paired with the CSV line that caused it.
+    #[derive(Debug, Error)]
+    #[error("Line {line}: {kind}")]
+    pub struct RowError {
+        line: u64,
+        kind: ErrorKind,
+    }
+    impl RowError {
+        /// Creates a new [`RowError`].
+        pub fn new(line: u64, kind: ErrorKind) -> Self {
+            Self { line, kind }
         }
+        /// Returns the line within the CSV that failed to build.
+        pub fn line(&self) -> u64 {
+            self.line
+        }
+        /// Returns a reference to the [`ErrorKind`] of this [`RowError`].
+        pub fn kind(&self) -> &ErrorKind {
+            &self.kind
+        }
+    }
+
+    /// The ways reading and building a plan from its rows can fail.
+    #[derive(Debug, Error)]
+    pub enum BuildError {
+        #[error("Unable to read the plan: {0}")]
+        Read(#[from] ErrorKind),
+        #[error("Plan was built, but it is empty.")]
+        Empty,
+        #[error("{} failed to build:\n{}", count_rows(.0.len()), join_lines(.0))]
+        Rows(Vec<RowError>),
+    }
+    /// Formats `count` as a number of rows, e.g. `1 row` or `2 rows`.
+    fn count_rows(count: usize) -> String {
+        match count {
+            1 => "1 row".to_string(),
+            _ => format!("{count} rows"),
+        }
+    }
+    /// Formats each [`RowError`] on its own line.
+    fn join_lines(errors: &[RowError]) -> String {
+        errors
+            .iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
     }
 }
@@ -1,81 +1,1319 @@
 #![allow(unused_imports)]
-#![allow(dead_code)]
 #![allow(unused_must_use)]
 
-use crate::sta::entry::Entry;
-use crate::sta::result::Result;
-use anyhow::anyhow;
+use crate::sta::entry::{count_cases, Carton, Entry, EntryWithUnit, MSKU_HEADER, PLAN_HEADERS};
+use crate::sta::validation::is_valid_fnsku;
+use crate::sta::weight::WeightUnit;
+use crate::sta::{BuildError, ErrorKind, Result, RowError};
 use serde::Serialize;
 use serde_json;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
 use std::path::Path;
 
-#[derive(Debug, Default)]
-struct Plan {
+#[derive(Debug, Default, Serialize)]
+pub struct Plan {
     entries: Vec<Entry>,
+    #[serde(skip)]
+    errors: Vec<RowError>,
 }
 
 impl Plan {
     /// Creates a new [`Plan`].
     fn new(entries: Vec<Entry>) -> Self {
-        Self { entries }
+        Self {
+            entries,
+            ..Default::default()
+        }
+    }
+    /**
+    Reads, builds, and sorts a [`Plan`] from a path that points to a CSV.
+
+    Rows that fail to build are discarded, see [`PlanBuilder::build`].
+
+    # Errors
+
+    This function will return an error if the file cannot be opened, the
+    CSV format is incorrect, or no row builds into a valid entry.
+    */
+    pub fn from_csv_path<P>(path: P) -> std::result::Result<Plan, BuildError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::build_sorted(PlanBuilder::from_csv_path(path)?)
+    }
+    /**
+    Reads, builds, and sorts a [`Plan`] from any reader of a CSV, such as
+    an open file or stdin.
+
+    Rows that fail to build are discarded, see [`PlanBuilder::build`].
+
+    # Errors
+
+    This function will return an error if the CSV format is incorrect, or no
+    row builds into a valid entry.
+    */
+    pub fn from_csv_reader<R>(reader: R) -> std::result::Result<Plan, BuildError>
+    where
+        R: std::io::Read,
+    {
+        Self::build_sorted(PlanBuilder::from_csv_reader(reader)?)
+    }
+    /// Builds the [`PlanBuilder`] into a sorted [`Plan`].
+    fn build_sorted(builder: PlanBuilder) -> std::result::Result<Plan, BuildError> {
+        let mut plan = builder.build()?;
+        plan.sort();
+        Ok(plan)
+    }
+    /**
+    Reads a CSV and pushes its valid entries onto the [`Plan`], returning how
+    many were added, e.g. to combine sheet tabs exported separately.
+
+    Rows that fail to build are discarded, as with [`PlanBuilder::build`]. If
+    the [`Plan`] was sorted beforehand (see [`Plan::sort`]), it is sorted
+    again once the entries are added.
+
+    # Errors
+
+    This function will return an error if the file cannot be opened, or the
+    CSV format is incorrect, see [`PlanBuilder::from_csv_path`].
+    */
+    pub fn append_csv_path<P: AsRef<Path>>(&mut self, path: P) -> Result<usize> {
+        let was_sorted = self.entries.is_sorted_by_key(sort_key);
+        let builder = PlanBuilder::from_csv_path(path)?;
+        let before = self.entries.len();
+        self.extend(builder.entries.into_iter().flatten());
+        if was_sorted {
+            self.sort();
+        };
+        Ok(self.entries.len() - before)
+    }
+    /// Push an [`Entry`] into the [`Plan`].
+    pub fn push(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+    /// Returns the number of entries in the [`Plan`].
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Returns `true` if the [`Plan`] contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Returns an iterator over the entries of the [`Plan`].
+    pub fn iter(&self) -> std::slice::Iter<'_, Entry> {
+        self.entries.iter()
+    }
+    /**
+    Returns the errors that were kept while building the [`Plan`].
+
+    This is always empty unless the [`PlanBuilder`] was built with
+    `keep_error` set to `true`.
+    */
+    pub fn errors(&self) -> &[RowError] {
+        &self.errors
+    }
+    /**
+    Returns the set of FNSKUs within the [`Plan`].
+
+    An FNSKU that appears on several entries is only included once.
+    */
+    pub fn fnsku_set(&self) -> HashSet<&str> {
+        self.entries
+            .iter()
+            .map(|entry| entry.get_fnsku())
+            .collect::<HashSet<&str>>()
+    }
+    /**
+    Returns every id shared by more than one [`Entry`], sorted.
+
+    Ids are expected to be unique, a duplicate usually means a row was
+    copied within the sheet.
+    */
+    pub fn duplicate_ids(&self) -> Vec<u32> {
+        let mut counts = BTreeMap::new();
+        for entry in &self.entries {
+            *counts.entry(entry.get_id()).or_insert(0u32) += 1;
+        }
+        counts
+            .into_iter()
+            .filter(|&(_, count)| count > 1)
+            .map(|(id, _)| id)
+            .collect()
+    }
+    /**
+    Returns the first [`Entry`] with the given `id`, the "Info" column of
+    the sheet, if there is one.
+    */
+    pub fn find_by_id(&self, id: u32) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.get_id() == id)
+    }
+    /**
+    Returns the sorted FNSKUs that appear both as a packed and as a loose
+    [`Entry`], which usually means a typo within the sheet.
+    */
+    pub fn mixed_pack_type_fnskus(&self) -> Vec<String> {
+        let packed = self
+            .entries
+            .iter()
+            .filter(|entry| entry.is_packed())
+            .map(|entry| entry.get_fnsku())
+            .collect::<HashSet<&str>>();
+        let mut mixed = self
+            .entries
+            .iter()
+            .filter(|entry| entry.is_loose() && packed.contains(entry.get_fnsku()))
+            .map(|entry| entry.get_fnsku().to_string())
+            .collect::<Vec<String>>();
+        mixed.sort_unstable();
+        mixed.dedup();
+        mixed
+    }
+    /// Returns `true` if any [`Entry`] in the [`Plan`] has the given `fnsku`.
+    pub fn contains_fnsku(&self, fnsku: &str) -> bool {
+        self.entries.iter().any(|entry| entry.get_fnsku() == fnsku)
+    }
+    /**
+    Returns the sum of units across every [`Entry`] in the [`Plan`].
+
+    The sum saturates at [`u32::MAX`] rather than overflowing.
+    */
+    pub fn total_units(&self) -> u32 {
+        self.entries
+            .iter()
+            .fold(0u32, |total, entry| total.saturating_add(entry.get_units()))
+    }
+    /**
+    Returns the total weight (in grams) of every [`Entry`] in the [`Plan`].
+
+    Returns `None` if any entry's weight cannot be computed, or if the sum
+    overflows.
+    */
+    pub fn total_gram_weight(&self) -> Option<u32> {
+        self.entries.iter().try_fold(0u32, |total, entry| {
+            total.checked_add(entry.try_total_gram_weight()?)
+        })
+    }
+    /**
+    Writes the [`Plan`] as a CSV shipping plan to `w`.
+
+    The header row matches the one expected when reading a plan, followed
+    by the MSKU column, so the output can be parsed back into a [`Plan`].
+
+    # Errors
+
+    This function will return an error if writing to `w` fails.
+    */
+    pub fn write_csv<W: std::io::Write>(&self, w: W) -> csv::Result<()> {
+        self.write_csv_with_unit(w, WeightUnit::default())
+    }
+    /**
+    Writes the [`Plan`] to `w` as a CSV, writing every weight as `unit`, see
+    [`Plan::write_csv`].
+
+    # Errors
+
+    This function will return an error if writing to `w` fails.
+    */
+    pub fn write_csv_with_unit<W: std::io::Write>(
+        &self,
+        w: W,
+        unit: WeightUnit,
+    ) -> csv::Result<()> {
+        let mut wtr = csv::Writer::from_writer(w);
+        wtr.write_record(PLAN_HEADERS.iter().chain([&MSKU_HEADER]))?;
+        for entry in &self.entries {
+            wtr.write_record(&entry.to_string_record_with_unit(unit))?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+    /**
+    Groups every loose [`Entry`] by its staging group name.
+
+    Packed entries have no staging group and are skipped.
+    */
+    pub fn group_by_staging_group(&self) -> HashMap<String, Vec<&Entry>> {
+        let mut groups: HashMap<String, Vec<&Entry>> = HashMap::new();
+        for entry in &self.entries {
+            let Some(group) = entry.try_group_name() else {
+                continue;
+            };
+            groups.entry(group.to_string()).or_default().push(entry);
+        }
+        groups
+    }
+    /**
+    Consumes the [`Plan`], splitting it into `(packed, loose, bare)` plans.
+
+    Entry order is preserved within each plan. Errors kept on the original
+    [`Plan`] do not belong to any pack type and are discarded.
+    */
+    pub fn split_by_pack_type(self) -> (Plan, Plan, Plan) {
+        let (packed, rest) = self
+            .entries
+            .into_iter()
+            .partition::<Vec<Entry>, _>(|entry| entry.is_packed());
+        let (loose, bare) = rest
+            .into_iter()
+            .partition::<Vec<Entry>, _>(|entry| entry.is_loose());
+        (Plan::new(packed), Plan::new(loose), Plan::new(bare))
+    }
+    /**
+    Returns a new [`Plan`] holding a clone of every [`Entry`] of `fnsku`.
+
+    Kept errors are not carried over.
+    */
+    pub fn filter_fnsku(&self, fnsku: &str) -> Plan {
+        self.entries
+            .iter()
+            .filter(|entry| entry.get_fnsku() == fnsku)
+            .cloned()
+            .collect()
+    }
+    /**
+    Returns a new [`Plan`] holding a clone of every loose [`Entry`] staged in
+    `group`.
+
+    Kept errors are not carried over.
+    */
+    pub fn retain_group(&self, group: &str) -> Plan {
+        self.entries
+            .iter()
+            .filter(|entry| entry.try_group_name() == Some(group))
+            .cloned()
+            .collect()
+    }
+    /// Moves every entry and kept error of `other` onto the end of the [`Plan`].
+    pub fn merge(&mut self, mut other: Plan) {
+        self.entries.append(&mut other.entries);
+        self.errors.append(&mut other.errors);
+    }
+    /**
+    Removes every [`Entry`] holding `fnsku`, returning how many were removed.
+    */
+    pub fn remove_by_fnsku(&mut self, fnsku: &str) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.get_fnsku() != fnsku);
+        before - self.entries.len()
+    }
+    /**
+    Sets the units of the only [`Entry`] holding `fnsku`, see
+    [`Entry::set_units`].
+
+    Entries sharing an FNSKU can be combined beforehand with
+    [`Plan::consolidate`].
+
+    # Errors
+
+    This function will return [`ErrorKind::FnskuNotFound`] if no entry holds
+    `fnsku`, [`ErrorKind::AmbiguousFnsku`] if several do, or
+    [`ErrorKind::NonDivisibleCaseQt`] if the entry is packed and `units` do
+    not fill its cases evenly.
+    */
+    pub fn set_units(&mut self, fnsku: &str, units: u32) -> Result<()> {
+        let mut matches = self
+            .entries
+            .iter_mut()
+            .filter(|entry| entry.get_fnsku() == fnsku)
+            .collect::<Vec<&mut Entry>>();
+        match matches.as_mut_slice() {
+            [] => Err(ErrorKind::FnskuNotFound {
+                fnsku: fnsku.to_string(),
+            }),
+            [entry] => entry.set_units(units),
+            many => Err(ErrorKind::AmbiguousFnsku {
+                fnsku: fnsku.to_string(),
+                count: many.len(),
+            }),
+        }
+    }
+    /**
+    Combines entries of the same FNSKU that are packed identically, summing
+    their units into the first occurrence.
+
+    Loose and packed entries of the same FNSKU stay separate, as do packed
+    entries with different cases. See [`Entry::try_consolidate`].
+    */
+    pub fn consolidate(&mut self) {
+        let mut consolidated: Vec<Entry> = Vec::with_capacity(self.entries.len());
+        for entry in self.entries.drain(..) {
+            if !consolidated.iter_mut().any(|c| c.try_consolidate(&entry)) {
+                consolidated.push(entry);
+            };
+        }
+        self.entries = consolidated;
+    }
+    /**
+    Returns the units of each FNSKU within the [`Plan`], summed across
+    entries and saturating at [`u32::MAX`].
+    */
+    pub fn units_by_fnsku(&self) -> BTreeMap<&str, u32> {
+        let mut map = BTreeMap::new();
+        for entry in &self.entries {
+            let units = map.entry(entry.get_fnsku()).or_insert(0u32);
+            *units = units.saturating_add(entry.get_units());
+        }
+        map
+    }
+    /**
+    Compares the units of each FNSKU in `self` against `other`.
+
+    `self` is treated as the original plan and `other` as the revision.
+    Entries are summed by FNSKU on both sides before comparing.
+    */
+    pub fn diff(&self, other: &Plan) -> PlanDiff {
+        let old = self.units_by_fnsku();
+        let new = other.units_by_fnsku();
+        let mut diff = PlanDiff::default();
+        for (fnsku, old_units) in &old {
+            match new.get(fnsku) {
+                None => diff.removed.push(fnsku.to_string()),
+                Some(new_units) if new_units != old_units => {
+                    diff.changed
+                        .push((fnsku.to_string(), *old_units, *new_units))
+                }
+                Some(_) => continue,
+            };
+        }
+        for fnsku in new.keys().filter(|fnsku| !old.contains_key(*fnsku)) {
+            diff.added.push(fnsku.to_string());
+        }
+        diff
+    }
+    /**
+    Summarizes the contents of the [`Plan`], see [`PlanSummary`].
+
+    Packed entries whose number of cases cannot be computed do not add to
+    the case count.
+    */
+    pub fn summarize(&self) -> PlanSummary {
+        let fnskus = self.fnsku_set();
+        let mut invalid_fnskus = fnskus
+            .iter()
+            .filter(|fnsku| !is_valid_fnsku(fnsku))
+            .map(|fnsku| fnsku.to_string())
+            .collect::<Vec<String>>();
+        invalid_fnskus.sort_unstable();
+        let packed = self.entries.iter().filter(|entry| entry.is_packed());
+        let total_cases = packed
+            .clone()
+            .filter_map(|entry| entry.num_of_cases().ok())
+            .fold(0u32, |total, cases| total.saturating_add(cases));
+        let packed_units = packed
+            .clone()
+            .fold(0u32, |total, entry| total.saturating_add(entry.get_units()));
+        let packed_count = packed.count();
+        let loose = self.entries.iter().filter(|entry| entry.is_loose());
+        let loose_units = loose
+            .clone()
+            .fold(0u32, |total, entry| total.saturating_add(entry.get_units()));
+        PlanSummary {
+            sku_count: fnskus.len(),
+            total_units: self.total_units(),
+            packed_count,
+            loose_count: loose.count(),
+            packed_units,
+            loose_units,
+            total_cases,
+            invalid_fnskus,
+        }
+    }
+    /**
+    Returns the total volume (in cubic inches) of every packed case in the
+    [`Plan`].
+
+    Loose entries contribute nothing, as their boxes are not known until
+    they are packed. Neither do packed entries whose number of cases cannot
+    be computed. The sum saturates at [`u64::MAX`].
+    */
+    pub fn total_cube_inches(&self) -> u64 {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let volume = entry.try_case_volume()?;
+                let cases = entry.num_of_cases().ok()?;
+                Some(volume.saturating_mul(cases as u64))
+            })
+            .fold(0u64, |total, volume| total.saturating_add(volume))
+    }
+    /**
+    Returns up to `n` entries with the greatest total weight, heaviest
+    first, see [`Entry::try_total_gram_weight`].
+
+    Entries whose weight cannot be computed are skipped. Ties keep the
+    order of the [`Plan`].
+    */
+    pub fn heaviest_entries(&self, n: usize) -> Vec<&Entry> {
+        let mut weighed = self
+            .entries
+            .iter()
+            .filter_map(|entry| Some((entry.try_total_gram_weight()?, entry)))
+            .collect::<Vec<(u32, &Entry)>>();
+        weighed.sort_by_key(|(grams, _)| std::cmp::Reverse(*grams));
+        weighed
+            .into_iter()
+            .take(n)
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+    /**
+    Returns up to `n` packed entries with the greatest case volume, largest
+    first.
+
+    Loose and bare entries have no case and are skipped. Ties keep the
+    order of the [`Plan`].
+    */
+    pub fn largest_cases(&self, n: usize) -> Vec<&Entry> {
+        let mut measured = self
+            .entries
+            .iter()
+            .filter_map(|entry| Some((entry.try_case_volume()?, entry)))
+            .collect::<Vec<(u64, &Entry)>>();
+        measured.sort_by_key(|(volume, _)| std::cmp::Reverse(*volume));
+        measured
+            .into_iter()
+            .take(n)
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+    /// Returns the total volume (in cubic feet) of every packed case, see
+    /// [`Plan::total_cube_inches`].
+    pub fn total_cube_feet(&self) -> f64 {
+        self.total_cube_inches() as f64 / 1728.0
+    }
+    /**
+    Checks every packed [`Entry`] against Amazon's standard carton limits,
+    see [`FBA_MAX_CARTON_INCHES`] and [`FBA_MAX_CARTON_GRAMS`].
+    */
+    pub fn fba_carton_violations(&self) -> Vec<CartonViolation> {
+        self.carton_violations(FBA_MAX_CARTON_INCHES, FBA_MAX_CARTON_GRAMS)
+    }
+    /**
+    Returns a [`CartonViolation`] for every packed [`Entry`] whose case has
+    a side longer than `max_inches`, or weighs more than `max_grams`.
+
+    An entry that breaks both limits produces a violation for each.
+    */
+    pub fn carton_violations(&self, max_inches: u32, max_grams: u32) -> Vec<CartonViolation> {
+        let mut violations = Vec::new();
+        for entry in &self.entries {
+            let fnsku = entry.get_fnsku().to_string();
+            if let Some((length, _, _)) = entry.try_case_dimensions() {
+                if length > max_inches {
+                    violations.push(CartonViolation::Oversize {
+                        fnsku: fnsku.clone(),
+                        inches: length,
+                    });
+                };
+            };
+            if let Some(grams) = entry.try_case_gram_weight() {
+                if grams > max_grams {
+                    violations.push(CartonViolation::Overweight { fnsku, grams });
+                };
+            };
+        }
+        violations
+    }
+    /**
+    Returns the sorted FNSKUs whose weight per unit exceeds
+    [`MAX_UNIT_GRAMS`], see [`Plan::suspicious_weights`].
+    */
+    pub fn default_suspicious_weights(&self) -> Vec<String> {
+        self.suspicious_weights(MAX_UNIT_GRAMS)
+    }
+    /**
+    Returns the sorted FNSKUs whose weight per unit exceeds `max_unit_grams`.
+
+    An absurd unit weight usually means the case weight was entered as the
+    unit weight, or the other way around. Packed entries derive their unit
+    weight from the case, see [`Entry::try_unit_gram_weight`]. Each FNSKU is
+    only listed once.
+    */
+    pub fn suspicious_weights(&self, max_unit_grams: u32) -> Vec<String> {
+        let mut fnskus = self
+            .entries
+            .iter()
+            .filter(|entry| {
+                entry
+                    .try_unit_gram_weight()
+                    .is_some_and(|grams| grams > max_unit_grams)
+            })
+            .map(|entry| entry.get_fnsku().to_string())
+            .collect::<Vec<String>>();
+        fnskus.sort_unstable();
+        fnskus.dedup();
+        fnskus
+    }
+    /**
+    Collects every structural problem within the [`Plan`] in a single pass,
+    rather than stopping at the first, see [`ValidationIssue`].
+
+    Checks for invalid FNSKUs, duplicate ids, packed entries whose units do
+    not divide into their cases, and cartons over Amazon's standard limits.
+    Rows kept as errors while building (see [`Plan::errors`]) are reported
+    when their units did not fill the cases evenly, or disagreed with the
+    declared total cases.
+
+    Such rows never became an [`Entry`], so their issues have no FNSKU (see
+    [`ValidationIssue::try_fnsku`]) and name the CSV line in their message
+    instead. They are only reported if the [`Plan`] was built with
+    `keep_error`, see [`PlanBuilder`]; otherwise they are silently missing.
+    */
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let duplicates = self.duplicate_ids();
+        for entry in &self.entries {
+            let fnsku = entry.get_fnsku();
+            if !is_valid_fnsku(fnsku) {
+                let message = format!("{fnsku:?} is not a valid FNSKU");
+                issues.push(ValidationIssue::new(
+                    fnsku,
+                    IssueKind::InvalidFnsku,
+                    message,
+                ));
+            };
+            if duplicates.contains(&entry.get_id()) {
+                let message = format!("Id {} is used by more than one entry", entry.get_id());
+                issues.push(ValidationIssue::new(fnsku, IssueKind::DuplicateId, message));
+            };
+            if let Err(error) = entry.num_of_cases() {
+                let kind = IssueKind::NonDivisibleCaseQt;
+                issues.push(ValidationIssue::new(fnsku, kind, error.to_string()));
+            };
+        }
+        for violation in self.fba_carton_violations() {
+            let message = match &violation {
+                CartonViolation::Oversize { inches, .. } => {
+                    format!("Case has a {inches} inch side, over {FBA_MAX_CARTON_INCHES}")
+                }
+                CartonViolation::Overweight { grams, .. } => {
+                    format!("Case weighs {grams} grams, over {FBA_MAX_CARTON_GRAMS}")
+                }
+            };
+            let kind = IssueKind::CartonLimit;
+            issues.push(ValidationIssue::new(violation.get_fnsku(), kind, message));
+        }
+        for error in &self.errors {
+            let kind = match error.kind() {
+                ErrorKind::NonDivisibleCaseQt => IssueKind::NonDivisibleCaseQt,
+                ErrorKind::TotalCasesMismatch { .. } => IssueKind::TotalCasesMismatch,
+                _ => continue,
+            };
+            issues.push(ValidationIssue {
+                fnsku: None,
+                kind,
+                message: error.to_string(),
+            });
+        }
+        issues
+    }
+    /**
+    Expands every packed [`Entry`] into one [`Carton`] per case, e.g. for a
+    pick sheet.
+
+    Loose entries are excluded, as they are not assigned to a carton until
+    they are packed.
+
+    # Errors
+
+    This function will return an error if any packed entry's number of
+    cases cannot be computed.
+    */
+    pub fn cartons(&self) -> Result<Vec<Carton>> {
+        let mut cartons = Vec::new();
+        for entry in &self.entries {
+            cartons.append(&mut entry.cartons()?);
+        }
+        // Expanding into cartons must never gain or lose a unit
+        debug_assert_eq!(
+            cartons.iter().map(|c| c.get_units() as u64).sum::<u64>(),
+            self.entries
+                .iter()
+                .filter(|entry| entry.is_packed())
+                .map(|entry| entry.get_units() as u64)
+                .sum::<u64>()
+        );
+        Ok(cartons)
+    }
+    /**
+    Re-cases every packed [`Entry`] whose case weighs more than
+    `max_case_grams` into lighter cases, see [`Entry::split_heavy_cases`].
+
+    Any remainder entry split off is given a fresh id, counting up from the
+    highest id within the [`Plan`], so ids stay unique.
+
+    # Errors
+
+    This function will return an error if any packed entry's number of
+    cases cannot be computed, leaving the [`Plan`] unchanged.
+    */
+    pub fn rebalance_cases(&mut self, max_case_grams: u32) -> Result<()> {
+        let rebalanced = self
+            .entries
+            .iter()
+            .map(|entry| entry.split_heavy_cases(max_case_grams))
+            .collect::<Result<Vec<Vec<Entry>>>>()?;
+        let mut next_id = self.entries.iter().map(Entry::get_id).max().unwrap_or(0);
+        let mut entries = Vec::new();
+        for split in rebalanced {
+            for (i, mut entry) in split.into_iter().enumerate() {
+                if i > 0 {
+                    next_id += 1;
+                    entry.set_id(next_id);
+                };
+                entries.push(entry);
+            }
+        }
+        self.entries = entries;
+        Ok(())
+    }
+    /**
+    Returns the units held once the [`Plan`] is boxed, the units of every
+    [`Carton`] plus the units of every loose [`Entry`].
+
+    Bare entries are excluded, as they have not been assigned to a box. The
+    sum saturates at [`u32::MAX`].
+
+    # Errors
+
+    This function will return an error if any packed entry's number of
+    cases cannot be computed, see [`Plan::cartons`].
+    */
+    pub fn carton_unit_total(&self) -> Result<u32> {
+        let carton_units = self.cartons()?.iter().fold(0u32, |total, carton| {
+            total.saturating_add(carton.get_units())
+        });
+        let loose_units = self
+            .entries
+            .iter()
+            .filter(|entry| entry.is_loose())
+            .fold(0u32, |total, entry| total.saturating_add(entry.get_units()));
+        Ok(carton_units.saturating_add(loose_units))
+    }
+    /**
+    Returns `true` if boxing the [`Plan`] accounts for every unit, see
+    [`Plan::carton_unit_total`].
+
+    Fails when a packed entry cannot be split into cases, or when bare
+    entries are yet to be given a pack type.
+    */
+    pub fn reconcile_units(&self) -> bool {
+        self.carton_unit_total()
+            .is_ok_and(|total| total == self.total_units())
+    }
+    /**
+    Returns the number of cases across every packed [`Entry`], e.g. for
+    pallet planning.
+
+    Loose entries count as zero, as their boxes are not known until they
+    are packed (see [`Plan::pack_loose`]). The sum saturates at
+    [`u32::MAX`].
+
+    # Errors
+
+    This function will return an error if any packed entry's number of
+    cases cannot be computed, see [`Entry::num_of_cases`].
+    */
+    pub fn total_cases(&self) -> Result<u32> {
+        Ok(self
+            .cases_by_fnsku()?
+            .values()
+            .fold(0u32, |total, cases| total.saturating_add(*cases)))
+    }
+    /**
+    Returns the number of cases for each FNSKU with a packed [`Entry`].
+
+    Loose entries are excluded, see [`Plan::total_cases`]. Sums saturate at
+    [`u32::MAX`].
+
+    # Errors
+
+    This function will return an error if any packed entry's number of
+    cases cannot be computed, see [`Entry::num_of_cases`].
+    */
+    pub fn cases_by_fnsku(&self) -> Result<HashMap<String, u32>> {
+        let mut map = HashMap::new();
+        for entry in self.entries.iter().filter(|entry| entry.is_packed()) {
+            let num_of_cases = entry.num_of_cases()?;
+            let cases = map.entry(entry.get_fnsku().to_string()).or_insert(0u32);
+            *cases = cases.saturating_add(num_of_cases);
+        }
+        Ok(map)
+    }
+    /**
+    Suggests how to pack the loose units of the [`Plan`] into boxes that
+    weigh at most `max_box_grams`.
+
+    Units are packed first-fit decreasing by their unit weight. Each staging
+    group is packed into its own boxes, so a box never mixes groups. A unit
+    that is heavier than `max_box_grams` on its own is placed in a box by
+    itself, making it the only way a box can exceed the limit.
+    */
+    pub fn pack_loose(&self, max_box_grams: u32) -> Vec<LooseBox> {
+        let mut boxes = Vec::new();
+        // Sort the groups so the boxes come out in a stable order
+        let groups = self
+            .group_by_staging_group()
+            .into_iter()
+            .collect::<BTreeMap<String, Vec<&Entry>>>();
+        for (group, entries) in groups {
+            // One (fnsku, grams) item per unit, heaviest first
+            let mut units = Vec::new();
+            for entry in entries {
+                let Some(grams) = entry.try_unit_gram_weight() else {
+                    continue;
+                };
+                let unit = (entry.get_fnsku(), grams);
+                units.extend(std::iter::repeat_n(unit, entry.get_units() as usize));
+            }
+            units.sort_by_key(|&(_, grams)| std::cmp::Reverse(grams));
+
+            let mut group_boxes: Vec<LooseBox> = Vec::new();
+            for (fnsku, grams) in units {
+                let fits = group_boxes
+                    .iter_mut()
+                    .find(|b| b.gram_weight.saturating_add(grams) <= max_box_grams);
+                let loose_box = match fits {
+                    Some(loose_box) => loose_box,
+                    None => {
+                        group_boxes.push(LooseBox::new(&group));
+                        group_boxes.last_mut().unwrap()
+                    }
+                };
+                loose_box.add_unit(fnsku, grams);
+            }
+            boxes.append(&mut group_boxes);
+        }
+        boxes
+    }
+    /**
+    Writes the box contents of every carton in the [`Plan`] as the
+    tab-delimited flat file Seller Central expects, one line per carton.
+
+    Boxes are numbered from 1 in carton order, weights are written in pounds
+    and dimensions in inches. Loose entries are not included, see
+    [`Plan::cartons`].
+
+    # Errors
+
+    This function will return an error if writing to `w` fails, or if the
+    cartons cannot be computed.
+    */
+    pub fn to_box_contents_tsv<W: std::io::Write>(&self, w: W) -> std::io::Result<()> {
+        let cartons = self
+            .cartons()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let mut wtr = csv::WriterBuilder::new().delimiter(b'\t').from_writer(w);
+        wtr.write_record([
+            "FNSKU",
+            "Quantity",
+            "Box Number",
+            "Box Weight (lb)",
+            "Box Length (in)",
+            "Box Width (in)",
+            "Box Height (in)",
+        ])?;
+        for (idx, carton) in cartons.iter().enumerate() {
+            wtr.write_record([
+                carton.get_fnsku().to_string(),
+                carton.get_units().to_string(),
+                (idx + 1).to_string(),
+                format!(
+                    "{:.2}",
+                    WeightUnit::Pounds.convert_grams(carton.get_gram_weight())
+                ),
+                carton.get_length().to_string(),
+                carton.get_width().to_string(),
+                carton.get_height().to_string(),
+            ])?;
+        }
+        wtr.flush()
+    }
+    /**
+    Returns a new [`Plan`] with every packed entry expanded into one entry
+    per case, see [`Entry::expand_cases`].
+
+    # Errors
+
+    This function will return an error if the number of cases of any packed
+    entry cannot be computed.
+    */
+    pub fn expand_entries(&self) -> Result<Plan> {
+        let expanded = self
+            .entries
+            .iter()
+            .map(Entry::expand_cases)
+            .collect::<Result<Vec<Vec<Entry>>>>()?;
+        Ok(expanded.into_iter().flatten().collect())
+    }
+    /**
+    Serializes the [`Plan`] into a compact JSON string.
+
+    Kept errors are not serialized.
+    */
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+    /**
+    Serializes the [`Plan`] into a pretty-printed JSON string.
+
+    Kept errors are not serialized.
+    */
+    pub fn to_json_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+    /**
+    Serializes the [`Plan`] into a compact JSON string, writing every weight
+    as `unit` rather than pounds, see [`Entry::with_unit`].
+
+    Kept errors are not serialized.
+    */
+    pub fn to_json_with_unit(&self, unit: WeightUnit) -> serde_json::Result<String> {
+        #[derive(Serialize)]
+        struct PlanWithUnit<'a> {
+            entries: Vec<EntryWithUnit<'a>>,
+        }
+        let entries = self.entries.iter().map(|e| e.with_unit(unit)).collect();
+        serde_json::to_string(&PlanWithUnit { entries })
+    }
+    /**
+    Builds the body of an SP-API inbound plan from the [`Plan`], holding an
+    `items` array with one object per [`Entry`].
+
+    Each item carries the `msku`, `quantity`, `labelOwner`, and `prepOwner`,
+    where the owners are e.g. `"AMAZON"`, `"SELLER"`, or `"NONE"`. No request
+    is sent.
+
+    # Errors
+
+    This function will return [`ErrorKind::MissingMsku`] for the first
+    [`Entry`] without an MSKU, as Amazon identifies items by MSKU alone.
+    */
+    pub fn to_inbound_plan_json(
+        &self,
+        label_owner: &str,
+        prep_owner: &str,
+    ) -> Result<serde_json::Value> {
+        let items = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let msku = entry.try_msku().ok_or_else(|| ErrorKind::MissingMsku {
+                    fnsku: entry.get_fnsku().to_string(),
+                })?;
+                Ok(serde_json::json!({
+                    "msku": msku,
+                    "quantity": entry.get_units(),
+                    "labelOwner": label_owner,
+                    "prepOwner": prep_owner,
+                }))
+            })
+            .collect::<Result<Vec<serde_json::Value>>>()?;
+        Ok(serde_json::json!({ "items": items }))
+    }
+    /**
+    Writes the [`Plan`] to `w` as pretty-printed JSON.
+
+    # Errors
+
+    This function will return an error if writing to `w` fails.
+    */
+    pub fn write_json<W: std::io::Write>(&self, w: W) -> serde_json::Result<()> {
+        serde_json::to_writer_pretty(w, self)
+    }
+    /**
+    Sorts the [`Plan`] in-place.
+
+    Sort order
+    * Pack type
+    * FNSKU
+    * Case Length
+    * Case Width
+    * Case Height
+    * Case Weight
+    * Group Name
+
+    */
+    pub fn sort(&mut self) {
+        self.entries.sort_unstable_by_key(sort_key);
+    }
+    /**
+    Sorts the [`Plan`] in-place by FNSKU, regardless of pack type.
+
+    Entries sharing an FNSKU fall back to the order of [`Plan::sort`].
+    */
+    pub fn sort_by_fnsku(&mut self) {
+        self.entries
+            .sort_unstable_by_key(|entry| (entry.get_fnsku().to_string(), sort_key(entry)));
+    }
+    /**
+    Sorts the [`Plan`] in-place by staging group name.
+
+    Entries without a group, such as packed entries, come first. Entries
+    sharing a group fall back to the order of [`Plan::sort`].
+    */
+    pub fn sort_by_group(&mut self) {
+        self.entries.sort_unstable_by_key(|entry| {
+            let group = entry.try_group_name().map(|group| group.to_string());
+            (group, sort_key(entry))
+        });
+    }
+}
+/// The key an [`Entry`] is ordered by, see [`Plan::sort`].
+type SortKey = (
+    bool,
+    String,
+    Option<u32>,
+    Option<u32>,
+    Option<u32>,
+    Option<u32>,
+    String,
+);
+/// Returns the [`SortKey`] of `entry`.
+fn sort_key(entry: &Entry) -> SortKey {
+    (
+        entry.is_loose(),
+        entry.get_fnsku().to_string(),
+        entry.try_case_length(),
+        entry.try_case_width(),
+        entry.try_case_height(),
+        entry.try_case_gram_weight(),
+        entry.try_group_name().unwrap_or_default().to_string(),
+    )
+}
+impl FromIterator<Entry> for Plan {
+    fn from_iter<T: IntoIterator<Item = Entry>>(iter: T) -> Self {
+        Plan::new(iter.into_iter().collect())
+    }
+}
+impl Extend<Entry> for Plan {
+    fn extend<T: IntoIterator<Item = Entry>>(&mut self, iter: T) {
+        self.entries.extend(iter);
+    }
+}
+impl IntoIterator for Plan {
+    type Item = Entry;
+    type IntoIter = std::vec::IntoIter<Entry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+/**
+The FNSKU level differences between two [`Plan`]s, see [`Plan::diff`].
+
+Every list is sorted by FNSKU.
+*/
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PlanDiff {
+    added: Vec<String>,
+    removed: Vec<String>,
+    changed: Vec<(String, u32, u32)>,
+}
+impl PlanDiff {
+    /// Returns the FNSKUs only found in the revised [`Plan`].
+    pub fn added(&self) -> &[String] {
+        &self.added
+    }
+    /// Returns the FNSKUs only found in the original [`Plan`].
+    pub fn removed(&self) -> &[String] {
+        &self.removed
+    }
+    /// Returns `(fnsku, old_units, new_units)` for each FNSKU whose units changed.
+    pub fn changed(&self) -> &[(String, u32, u32)] {
+        &self.changed
+    }
+    /// Returns `true` if both [`Plan`]s hold the same units of each FNSKU.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+/**
+A suggested box of loose units from a single staging group, see
+[`Plan::pack_loose`].
+*/
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LooseBox {
+    group: String,
+    contents: Vec<(String, u32)>,
+    gram_weight: u32,
+}
+impl LooseBox {
+    /// Creates a new, empty [`LooseBox`] for the staging `group`.
+    fn new(group: &str) -> Self {
+        Self {
+            group: group.to_string(),
+            ..Default::default()
+        }
+    }
+    /// Adds a single unit of `fnsku` weighing `grams` to the [`LooseBox`].
+    fn add_unit(&mut self, fnsku: &str, grams: u32) {
+        self.gram_weight = self.gram_weight.saturating_add(grams);
+        match self.contents.iter_mut().find(|(f, _)| f == fnsku) {
+            Some((_, units)) => *units += 1,
+            None => self.contents.push((fnsku.to_string(), 1)),
+        };
+    }
+    /// Returns a reference to the staging group of the [`LooseBox`].
+    pub fn get_group(&self) -> &str {
+        &self.group
+    }
+    /// Returns `(fnsku, units)` for each FNSKU within the [`LooseBox`].
+    pub fn get_contents(&self) -> &[(String, u32)] {
+        &self.contents
+    }
+    /// Returns the weight (in grams) of the units within the [`LooseBox`].
+    pub fn get_gram_weight(&self) -> u32 {
+        self.gram_weight
+    }
+}
+/// The longest side (in inches) Amazon accepts on a standard carton.
+const FBA_MAX_CARTON_INCHES: u32 = 25;
+/// The heaviest weight (in grams) Amazon accepts on a standard carton, 50 lb.
+const FBA_MAX_CARTON_GRAMS: u32 = 22680;
+/// The heaviest weight (in grams) expected of a single unit, 50 lb.
+const MAX_UNIT_GRAMS: u32 = 22680;
+/**
+A packed case that breaks a carton limit, see [`Plan::carton_violations`].
+
+Each variant holds the FNSKU of the entry along with the offending value.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CartonViolation {
+    Oversize { fnsku: String, inches: u32 },
+    Overweight { fnsku: String, grams: u32 },
+}
+impl CartonViolation {
+    /// Returns a reference to the FNSKU of this [`CartonViolation`].
+    pub fn get_fnsku(&self) -> &str {
+        match self {
+            CartonViolation::Oversize { fnsku, .. } => fnsku,
+            CartonViolation::Overweight { fnsku, .. } => fnsku,
+        }
+    }
+}
+/// The kinds of problem reported by [`Plan::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    InvalidFnsku,
+    DuplicateId,
+    NonDivisibleCaseQt,
+    CartonLimit,
+    TotalCasesMismatch,
+}
+/**
+A single structural problem within a [`Plan`], see [`Plan::validate`].
+
+Holds the FNSKU of the offending entry, along with a readable message. Rows
+that failed to build have no FNSKU to report.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    fnsku: Option<String>,
+    kind: IssueKind,
+    message: String,
+}
+impl ValidationIssue {
+    /// Creates a new [`ValidationIssue`] for the entry holding `fnsku`.
+    fn new(fnsku: &str, kind: IssueKind, message: String) -> Self {
+        Self {
+            fnsku: Some(fnsku.to_string()),
+            kind,
+            message,
+        }
     }
-    /// Push an [`Entry`] into the [`Plan`].
-    fn push(&mut self, entry: Entry) {
-        self.entries.push(entry);
+    /// Returns the FNSKU of the offending entry, if it was built.
+    pub fn try_fnsku(&self) -> Option<&str> {
+        self.fnsku.as_deref()
+    }
+    /// Returns the [`IssueKind`] of this [`ValidationIssue`].
+    pub fn kind(&self) -> IssueKind {
+        self.kind
+    }
+    /// Returns a reference to the message of this [`ValidationIssue`].
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+/**
+An overview of a [`Plan`], see [`Plan::summarize`].
+
+* `sku_count`: number of distinct FNSKUs
+* `total_units`: units across every entry
+* `packed_count` / `loose_count`: number of entries of each pack type
+* `packed_units` / `loose_units`: units across the entries of each pack type
+* `total_cases`: cases across every packed entry
+* `invalid_fnskus`: sorted FNSKUs that fail [`is_valid_fnsku`]
+*/
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PlanSummary {
+    sku_count: usize,
+    total_units: u32,
+    packed_count: usize,
+    loose_count: usize,
+    packed_units: u32,
+    loose_units: u32,
+    total_cases: u32,
+    invalid_fnskus: Vec<String>,
+}
+impl PlanSummary {
+    /// Returns the number of distinct FNSKUs.
+    pub fn sku_count(&self) -> usize {
+        self.sku_count
+    }
+    /// Returns the units across every entry.
+    pub fn total_units(&self) -> u32 {
+        self.total_units
+    }
+    /// Returns the number of packed entries.
+    pub fn packed_count(&self) -> usize {
+        self.packed_count
+    }
+    /// Returns the number of loose entries.
+    pub fn loose_count(&self) -> usize {
+        self.loose_count
+    }
+    /// Returns the cases across every packed entry.
+    pub fn total_cases(&self) -> u32 {
+        self.total_cases
     }
     /**
-    Sorts the [`Plan`] in-place.
+    Returns the fraction of the units that are packed, from `0.0` to `1.0`.
 
-    Sort order
-    * Pack type
-    * FNSKU
-    * Case Length
-    * Case Width
-    * Case Height
-    * Case Weight
-    * Group Name
+    Returns `0.0` if the [`Plan`] holds no units.
+    */
+    pub fn packed_unit_share(&self) -> f64 {
+        unit_share(self.packed_units, self.total_units)
+    }
+    /**
+    Returns the fraction of the units that are loose, from `0.0` to `1.0`.
 
+    Returns `0.0` if the [`Plan`] holds no units.
     */
-    fn sort(&mut self) {
-        self.entries.sort_unstable_by_key(|entry| {
-            (
-                entry.is_loose(),
-                entry.get_fnsku().to_string(),
-                entry.try_case_length(),
-                entry.try_case_width(),
-                entry.try_case_height(),
-                entry.try_case_gram_weight(),
-                entry.try_group_name().unwrap_or_default().to_string(),
-            )
-        });
+    pub fn loose_unit_share(&self) -> f64 {
+        unit_share(self.loose_units, self.total_units)
+    }
+    /// Returns the FNSKUs that fail [`is_valid_fnsku`].
+    pub fn invalid_fnskus(&self) -> &[String] {
+        &self.invalid_fnskus
+    }
+    /// Returns `true` if any FNSKU in the [`Plan`] is invalid.
+    pub fn has_invalid(&self) -> bool {
+        !self.invalid_fnskus.is_empty()
+    }
+}
+/// Returns `units` as a fraction of `total_units`, or `0.0` if there are none.
+fn unit_share(units: u32, total_units: u32) -> f64 {
+    if total_units == 0 {
+        return 0.0;
+    };
+    units as f64 / total_units as f64
+}
+impl std::fmt::Display for PlanSummary {
+    /// Formats the [`PlanSummary`] as a short, multi-line report.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} SKUs, {} units", self.sku_count, self.total_units)?;
+        writeln!(
+            f,
+            "{} in {}",
+            count_entries(self.packed_count, "packed"),
+            count_cases(self.total_cases)
+        )?;
+        write!(f, "{}", count_entries(self.loose_count, "loose"))?;
+        if self.has_invalid() {
+            write!(f, "\nInvalid FNSKUs: {}", self.invalid_fnskus.join(", "))?;
+        };
+        Ok(())
+    }
+}
+/// Formats `count` as a number of entries, e.g. `1 loose entry` or `2 loose entries`.
+fn count_entries(count: usize, pack_type: &str) -> String {
+    match count {
+        1 => format!("1 {pack_type} entry"),
+        _ => format!("{count} {pack_type} entries"),
     }
 }
-#[derive(Debug, Default)]
+#[derive(Debug)]
 /**
 Convenient builder for a [`Plan`].
 
 Comes with various default options, all of which can be changed prior to
-building.
+building. Options are set fluently, e.g.
+`PlanBuilder::default().with_delimiter(b'\t').read_csv_path(path)`.
 
 Options:
 * `keep_error`: default `false`
-    * Discards all errors
+    * `false` discards all errors during [`PlanBuilder::build`]
+    * `true` moves errors into [`Plan::errors`], except for rows that are
+      missing an FNSKU, which are treated as blank rows and discarded
+* `strict`: default `false`
+    * `true` fails [`PlanBuilder::build`] if any row fails to build, again
+      excluding rows that are missing an FNSKU
+* `delimiter`: default `b','`
+    * the byte separating fields when reading a CSV, e.g. `b'\t'`
+* `weight_unit`: default [`WeightUnit::Pounds`]
+    * the unit every weight is read in when reading a CSV
 */
-struct PlanBuilder {
-    entries: Vec<Result<Entry>>,
+pub struct PlanBuilder {
+    entries: Vec<std::result::Result<Entry, RowError>>,
     keep_error: bool,
+    strict: bool,
+    delimiter: u8,
+    weight_unit: WeightUnit,
+}
+
+impl Default for PlanBuilder {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            keep_error: false,
+            strict: false,
+            delimiter: b',',
+            weight_unit: WeightUnit::default(),
+        }
+    }
 }
 
 impl PlanBuilder {
+    /// Sets the `keep_error` option, see [`PlanBuilder`].
+    pub fn with_keep_error(mut self, keep_error: bool) -> Self {
+        self.keep_error = keep_error;
+        self
+    }
+    /// Sets the `strict` option, see [`PlanBuilder`].
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    /// Sets the byte separating fields when reading a CSV, see [`PlanBuilder`].
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+    /// Sets the unit every weight is read in when reading a CSV, see [`PlanBuilder`].
+    pub fn with_weight_unit(mut self, weight_unit: WeightUnit) -> Self {
+        self.weight_unit = weight_unit;
+        self
+    }
     /**
-    Push a `Result<Entry>` to the plan
+    Push a `Result<Entry>` to the plan, along with the CSV line it came from.
 
     The builder holds `Result` wrapped entries to have the control over
-    which options are discarded prior to building.
+    which options are discarded prior to building. Any error is wrapped in a
+    [`RowError`] so the line can be reported later.
+    */
+    fn push(&mut self, line: u64, e: Result<Entry>) {
+        self.entries
+            .push(e.map_err(|kind| RowError::new(line, kind)))
+    }
+    /**
+    Returns an iterator over every [`RowError`] currently held.
+
+    Useful for listing every problem within a CSV prior to building.
     */
-    fn push(&mut self, e: Result<Entry>) {
-        self.entries.push(e)
+    pub fn errors(&self) -> impl Iterator<Item = &RowError> {
+        self.entries.iter().filter_map(|x| x.as_ref().err())
     }
 
     /**
@@ -83,52 +1321,162 @@ impl PlanBuilder {
 
     # Errors
 
-    This function will return an error if the CSV format is incorrect, or
-    deserialization fails to return a valid entry.
+    This function will return an [`ErrorKind::Io`] if the file cannot be
+    opened, such as when it does not exist, or an [`ErrorKind::CsvError`] if
+    the CSV format is incorrect.
+    */
+    pub fn from_csv_path<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::default().read_csv_path(path)
+    }
+    /**
+    Reads every row of the CSV at `path` into the [`PlanBuilder`], using the
+    configured delimiter and weight unit.
+
+    # Errors
+
+    This function will return an [`ErrorKind::Io`] if the file cannot be
+    opened, or an [`ErrorKind::CsvError`] if the CSV format is incorrect.
+    */
+    pub fn read_csv_path<P>(self, path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = std::fs::File::open(path)?;
+        self.read_csv_reader(file)
+    }
+    /**
+    Construct a [`Plan`] from a path that points to a CSV, building the
+    entries in parallel.
+
+    Every record is read before any entry is built, so this is best suited
+    to large plans. Entries keep the order they had within the CSV.
+
+    # Errors
+
+    This function will return an [`ErrorKind::Io`] if the file cannot be
+    opened, or an [`ErrorKind::CsvError`] if the CSV format is incorrect.
     */
-    fn from_csv_path<P>(path: P) -> Result<Self>
+    #[cfg(feature = "rayon")]
+    pub fn from_csv_path_parallel<P>(path: P) -> Result<Self>
     where
         P: AsRef<Path>,
     {
+        use rayon::prelude::*;
+
+        let file = std::fs::File::open(path)?;
+        let records = csv::Reader::from_reader(file)
+            .into_records()
+            .collect::<csv::Result<Vec<csv::StringRecord>>>()?;
+        let mut built = records
+            .into_par_iter()
+            .enumerate()
+            .map(|(index, record)| {
+                let line = record.position().map_or(0, |pos| pos.line());
+                (index, line, Entry::from_csv_record(record))
+            })
+            .collect::<Vec<_>>();
+        // Sort back into the CSV's order so the output is deterministic
+        built.sort_unstable_by_key(|(index, _, _)| *index);
+
         let mut pb = Self::default();
-        let csv_reader = csv::Reader::from_path(path)?;
+        for (_, line, entry) in built {
+            pb.push(line, entry);
+        }
+        Ok(pb)
+    }
+    /**
+    Construct a [`Plan`] from any reader that yields a CSV, such as an
+    in-memory download or stdin.
+
+    # Errors
+
+    This function will return an error if the CSV format is incorrect, or
+    deserialization fails to return a valid entry.
+    */
+    pub fn from_csv_reader<R>(reader: R) -> Result<Self>
+    where
+        R: std::io::Read,
+    {
+        Self::default().read_csv_reader(reader)
+    }
+    /**
+    Reads every row of a CSV from `reader` into the [`PlanBuilder`], using
+    the configured delimiter and weight unit.
+
+    # Errors
+
+    This function will return an error if the CSV format is incorrect.
+    */
+    pub fn read_csv_reader<R>(mut self, reader: R) -> Result<Self>
+    where
+        R: std::io::Read,
+    {
+        let csv_reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .from_reader(reader);
         for wrapped_record in csv_reader.into_records() {
             let record = wrapped_record?;
-            pb.push(Entry::from_csv_record(record));
+            let line = record.position().map_or(0, |pos| pos.line());
+            let entry = Entry::from_csv_record_with_unit(record, self.weight_unit);
+            self.push(line, entry);
         }
-        Ok(pb)
+        Ok(self)
+    }
+    /**
+    Construct a [`Plan`] from the contents of a CSV, such as an inline
+    string.
+
+    # Errors
+
+    This function will return an error if the CSV format is incorrect, see
+    [`PlanBuilder::from_csv_reader`].
+    */
+    pub fn from_csv_str(s: &str) -> Result<Self> {
+        Self::from_csv_reader(s.as_bytes())
     }
     /**
     Consume the [`PlanBuilder`] and return the generate [`Plan`].
 
     # Errors
 
-    This function will return an error if the resulting [`Plan`] is empty once
-    all of the errors are removed.
+    This function will return [`BuildError::Empty`] if the resulting [`Plan`]
+    is empty once all of the errors are removed. When `strict` is set, it will
+    also return [`BuildError::Rows`] holding every row that failed to build.
     */
-    fn build(mut self) -> std::result::Result<Plan, anyhow::Error> {
-        if self.keep_error {
+    pub fn build(mut self) -> std::result::Result<Plan, BuildError> {
+        if self.keep_error || self.strict {
             self.remove_entries_without_fnskus();
         };
 
-        let entry_vec = self
-            .entries
-            .into_iter()
-            .filter_map(|x| x.ok())
-            .collect::<Vec<Entry>>();
-        let plan = Plan::new(entry_vec);
+        let mut entry_vec = Vec::new();
+        let mut error_vec = Vec::new();
+        for wrapped_entry in self.entries {
+            match wrapped_entry {
+                Ok(entry) => entry_vec.push(entry),
+                Err(error) if self.keep_error || self.strict => error_vec.push(error),
+                Err(_) => continue,
+            };
+        }
+        if self.strict && !error_vec.is_empty() {
+            return Err(BuildError::Rows(error_vec));
+        };
+        let mut plan = Plan::new(entry_vec);
+        plan.errors = error_vec;
         if plan.entries.is_empty() {
-            Err(anyhow!("Plan was built, but it is empty."))
+            Err(BuildError::Empty)
         } else {
             Ok(plan)
         }
     }
     /// Remove any [`Entry`] that is missing FNSKUs.
     fn remove_entries_without_fnskus(&mut self) {
-        use crate::sta::result::ErrorKind; // TODO get rid of this
-        self.entries.drain_filter(|x| {
-            x.as_ref()
-                .is_err_and(|x| matches!(x, ErrorKind::MissingFnsku))
+        // Keep everything except errors caused by a missing FNSKU
+        self.entries.retain(|x| {
+            !x.as_ref()
+                .is_err_and(|x| matches!(x.kind(), ErrorKind::MissingFnsku))
         });
     }
 }
@@ -136,10 +1484,19 @@ impl PlanBuilder {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::sta::entry::EntryParser;
     fn load_csv_into_builder() -> Result<PlanBuilder> {
         static TEST_PLAN: &str = "tests/data/STAPlan.csv";
         PlanBuilder::from_csv_path(TEST_PLAN)
     }
+    fn plan_from_rows(rows: &[&str]) -> Plan {
+        let entries = rows
+            .iter()
+            .map(|row| csv::StringRecord::from(row.split(',').collect::<Vec<_>>()))
+            .map(|record| Entry::from_csv_record(record).unwrap())
+            .collect::<Vec<Entry>>();
+        Plan::new(entries)
+    }
     #[test]
     fn unwrap_plan_builder() {
         let builder = load_csv_into_builder();
@@ -156,4 +1513,815 @@ mod test {
         let mut plan = builder.build().unwrap();
         plan.sort();
     }
+    #[test]
+    fn build_discards_errors_by_default() {
+        let builder = load_csv_into_builder().unwrap();
+        let plan = builder.build().unwrap();
+        assert_eq!(plan.entries.len(), 12);
+        assert!(plan.errors().is_empty());
+    }
+    #[test]
+    fn build_keeps_errors_when_requested() {
+        let mut builder = load_csv_into_builder().unwrap();
+        builder.keep_error = true;
+        let plan = builder.build().unwrap();
+        assert_eq!(plan.entries.len(), 12);
+        assert_eq!(plan.errors().len(), 1);
+        assert!(matches!(
+            plan.errors()[0].kind(),
+            ErrorKind::InvalidPackType
+        ));
+    }
+    #[test]
+    fn len_and_iter_of_built_plan() {
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        assert_eq!(plan.len(), 12);
+        assert!(!plan.is_empty());
+        assert_eq!(plan.iter().count(), plan.len());
+        assert_eq!(plan.into_iter().count(), 12);
+        assert!(Plan::default().is_empty());
+    }
+    #[test]
+    fn total_units_of_built_plan() {
+        let builder = load_csv_into_builder().unwrap();
+        let plan = builder.build().unwrap();
+        assert_eq!(plan.total_units(), 174);
+    }
+    #[test]
+    fn total_gram_weight_of_mixed_plan() {
+        let plan = plan_from_rows(&[
+            // 5 cases at 9072g each
+            "1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            // 12 units at 227g each
+            "2,X001ABCDE3,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        assert_eq!(plan.total_gram_weight(), Some(5 * 9072 + 12 * 227));
+    }
+    #[test]
+    fn write_csv_round_trip() {
+        let builder = load_csv_into_builder().unwrap();
+        let plan = builder.build().unwrap();
+        let mut buf = Vec::new();
+        plan.write_csv(&mut buf).unwrap();
+
+        let rdr = csv::Reader::from_reader(buf.as_slice());
+        let reparsed = rdr
+            .into_records()
+            .map(|record| Entry::from_csv_record(record.unwrap()).unwrap())
+            .collect::<Vec<Entry>>();
+        assert_eq!(reparsed.len(), plan.entries.len());
+
+        let data = "Info,FNSKU,MSKU,Quantity,Pack Type,Staging Group,Unit Weight\n\
+            1,X001AAAAA1,MSKU-1,12,Loose,GroupA,0.5\n\
+            2,X001AAAAA2,,4,Loose,GroupA,0.25\n";
+        let plan = Plan::new(EntryParser::from_reader_with_headers(data.as_bytes()).unwrap());
+        let mut buf = Vec::new();
+        plan.write_csv(&mut buf).unwrap();
+        let reparsed = PlanBuilder::from_csv_reader(buf.as_slice())
+            .unwrap()
+            .build()
+            .unwrap();
+        let mskus = reparsed.iter().map(Entry::try_msku).collect::<Vec<_>>();
+        assert_eq!(mskus, vec![Some("MSKU-1"), None]);
+    }
+    #[test]
+    fn export_in_grams() {
+        let plan = plan_from_rows(&["1,X001AAAAA1,12,Loose,GroupA,1,,,,,,"]);
+        let mut buf = Vec::new();
+        plan.write_csv_with_unit(&mut buf, WeightUnit::Grams)
+            .unwrap();
+        let csv = String::from_utf8(buf).unwrap();
+        assert!(csv.contains(",454.00,"));
+
+        let pounds = plan.to_json_with_unit(WeightUnit::Pounds).unwrap();
+        assert_eq!(pounds, plan.to_json().unwrap());
+        let json = plan.to_json_with_unit(WeightUnit::Grams).unwrap();
+        assert!(json.contains("\"Unit Weight\":454.0"));
+    }
+    #[test]
+    fn inbound_plan_json() {
+        let data = "Info,FNSKU,MSKU,Quantity,Pack Type,Staging Group,Unit Weight\n\
+            1,X001AAAAA1,MSKU-1,12,Loose,GroupA,0.5\n\
+            2,X001AAAAA2,MSKU-2,4,Loose,GroupA,0.25\n";
+        let plan = Plan::new(EntryParser::from_reader_with_headers(data.as_bytes()).unwrap());
+        let json = plan.to_inbound_plan_json("SELLER", "NONE").unwrap();
+        let items = json["items"].as_array().unwrap();
+        assert_eq!(items.len(), plan.len());
+        for (item, entry) in items.iter().zip(plan.iter()) {
+            assert_eq!(item["msku"], entry.try_msku().unwrap());
+            assert_eq!(item["quantity"], entry.get_units());
+            assert_eq!(item["labelOwner"], "SELLER");
+            assert_eq!(item["prepOwner"], "NONE");
+        }
+
+        // The fixture has no MSKU column
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        let error = plan.to_inbound_plan_json("SELLER", "NONE").unwrap_err();
+        assert!(matches!(error, ErrorKind::MissingMsku { fnsku } if fnsku == "X001AAAAA1"));
+    }
+    #[test]
+    fn from_csv_path_skips_utf8_bom() {
+        static TEST_PLAN_BOM: &str = "tests/data/STAPlanBom.csv";
+        let plan = PlanBuilder::from_csv_path(TEST_PLAN_BOM)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan.entries[0].to_string_record().get(0), Some("1"));
+    }
+    #[test]
+    fn from_csv_path_failure_modes() {
+        let missing = PlanBuilder::from_csv_path("tests/data/DoesNotExist.csv").unwrap_err();
+        assert!(
+            matches!(missing, ErrorKind::Io(ref e) if e.kind() == std::io::ErrorKind::NotFound)
+        );
+
+        let empty = PlanBuilder::from_csv_reader(&b""[..]).unwrap();
+        assert!(empty.build().is_err());
+
+        let malformed = "Info,FNSKU\n1,X001AAAAA1,50\n";
+        let malformed = PlanBuilder::from_csv_reader(malformed.as_bytes()).unwrap_err();
+        assert!(matches!(malformed, ErrorKind::CsvError(_)));
+
+        assert!(load_csv_into_builder().unwrap().build().is_ok());
+    }
+    #[test]
+    fn plan_from_csv_path_errors_are_distinct() {
+        let missing = Plan::from_csv_path("tests/data/DoesNotExist.csv").unwrap_err();
+        assert!(matches!(missing, BuildError::Read(ErrorKind::Io(_))));
+
+        let headers = PLAN_HEADERS.join(",");
+        let empty = Plan::from_csv_reader(headers.as_bytes()).unwrap_err();
+        assert!(matches!(empty, BuildError::Empty));
+
+        let malformed = "Info,FNSKU\n1,X001AAAAA1,50\n";
+        let malformed = Plan::from_csv_reader(malformed.as_bytes()).unwrap_err();
+        assert!(matches!(
+            malformed,
+            BuildError::Read(ErrorKind::CsvError(_))
+        ));
+    }
+    #[test]
+    fn plan_from_csv_path_is_sorted() {
+        let plan = Plan::from_csv_path("tests/data/STAPlan.csv").unwrap();
+        let mut sorted = load_csv_into_builder().unwrap().build().unwrap();
+        sorted.sort();
+        assert_eq!(plan.len(), sorted.len());
+        let fnskus = |plan: &Plan| {
+            plan.iter()
+                .map(|e| e.get_fnsku().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(fnskus(&plan), fnskus(&sorted));
+    }
+    #[test]
+    fn expand_entries_by_case_count() {
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,50,Packed,,,10,12,10,8,20,5",
+            "2,X001AAAAA2,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        let expanded = plan.expand_entries().unwrap();
+        assert_eq!(expanded.len(), 6);
+        let cases = expanded.iter().filter(|e| e.is_packed());
+        assert_eq!(cases.clone().count(), 5);
+        assert!(cases.clone().all(|e| e.get_units() == 10));
+        assert_eq!(expanded.total_units(), plan.total_units());
+    }
+    #[test]
+    fn collect_and_extend_plan() {
+        let entries = load_csv_into_builder().unwrap().build().unwrap().entries;
+        let count = entries.len();
+        let mut plan = entries.clone().into_iter().collect::<Plan>();
+        assert_eq!(plan.len(), count);
+        plan.extend(entries);
+        assert_eq!(plan.len(), count * 2);
+    }
+    #[test]
+    fn fba_carton_violations() {
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        assert!(plan.fba_carton_violations().is_empty());
+
+        let oversize = plan_from_rows(&["1,X001AAAAA1,50,Packed,,,10,30,10,8,20,5"]);
+        let violations = oversize.fba_carton_violations();
+        let expect = CartonViolation::Oversize {
+            fnsku: "X001AAAAA1".to_string(),
+            inches: 30,
+        };
+        assert_eq!(violations, vec![expect]);
+
+        // 30 kg is roughly 66.14 lb
+        let overweight = plan_from_rows(&["1,X001AAAAA1,50,Packed,,,10,12,10,8,66.14,5"]);
+        let violations = overweight.fba_carton_violations();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].get_fnsku(), "X001AAAAA1");
+        assert!(matches!(
+            violations[0],
+            CartonViolation::Overweight { grams, .. } if grams > FBA_MAX_CARTON_GRAMS
+        ));
+    }
+    #[test]
+    fn strict_build_rejects_failed_rows() {
+        let csv = format!(
+            "{}\n{}\n{}\n",
+            PLAN_HEADERS.join(","),
+            "1,X001AAAAA1,50,Packed,,,10,12,10,8,20,5",
+            "2,X001AAAAA2,45,Packed,,,10,12,10,8,20,",
+        );
+        let strict = PlanBuilder::default()
+            .with_strict(true)
+            .read_csv_reader(csv.as_bytes())
+            .unwrap();
+        let error = strict.build().unwrap_err().to_string();
+        assert!(error.starts_with("1 row failed to build"));
+        assert!(error.contains("Line 3"));
+
+        let lenient = PlanBuilder::from_csv_reader(csv.as_bytes()).unwrap();
+        let plan = lenient.build().unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan.entries[0].get_fnsku(), "X001AAAAA1");
+    }
+    #[test]
+    fn build_all_invalid_is_empty() {
+        let csv = format!(
+            "{}\n{}\n{}\n",
+            PLAN_HEADERS.join(","),
+            "1,X001AAAAA1,45,Packed,,,10,12,10,8,20,",
+            "2,X001AAAAA2,12,Loose,,0.5,,,,,,",
+        );
+        let builder = PlanBuilder::from_csv_str(&csv).unwrap();
+        assert!(matches!(builder.build(), Err(BuildError::Empty)));
+
+        let builder = PlanBuilder::from_csv_str(&csv).unwrap();
+        let strict = builder.with_strict(true);
+        let Err(BuildError::Rows(rows)) = strict.build() else {
+            panic!("expected every row to be reported");
+        };
+        assert_eq!(rows.len(), 2);
+        assert!(matches!(rows[1].kind(), ErrorKind::MissingGroup));
+        let error = BuildError::Rows(rows).to_string();
+        assert!(error.starts_with("2 rows failed to build"));
+    }
+    #[test]
+    fn fnsku_lookups() {
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        assert!(plan.contains_fnsku("X001AAAAA1"));
+        assert!(!plan.contains_fnsku("X000000000"));
+        let doubled = plan.iter().chain(plan.iter()).cloned().collect::<Plan>();
+        assert_eq!(doubled.fnsku_set(), plan.fnsku_set());
+        assert_eq!(plan.fnsku_set().len(), 12);
+    }
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_parse_matches_sequential() {
+        static TEST_PLAN: &str = "tests/data/STAPlan.csv";
+        let parallel = PlanBuilder::from_csv_path_parallel(TEST_PLAN).unwrap();
+        let sequential = load_csv_into_builder().unwrap();
+        assert_eq!(parallel.entries.len(), sequential.entries.len());
+        for (a, b) in parallel.entries.iter().zip(&sequential.entries) {
+            match (a, b) {
+                (Ok(a), Ok(b)) => assert_eq!(a.to_string_record(), b.to_string_record()),
+                (Err(a), Err(b)) => assert_eq!(a.to_string(), b.to_string()),
+                _ => panic!("parallel and sequential results differ"),
+            }
+        }
+    }
+    #[test]
+    fn sort_variants() {
+        let rows = [
+            "1,X001AAAAA3,2,Loose,GroupB,1,,,,,,",
+            "2,X001AAAAA2,10,Packed,,,10,12,10,8,20,",
+            "3,X001AAAAA1,3,Loose,GroupA,1,,,,,,",
+            "4,X001AAAAA4,10,Packed,,,10,12,10,8,20,",
+        ];
+        let fnskus = |plan: &Plan| {
+            plan.iter()
+                .map(|e| e.get_fnsku()[9..].to_string())
+                .collect::<String>()
+        };
+
+        let mut plan = plan_from_rows(&rows);
+        plan.sort();
+        assert_eq!(fnskus(&plan), "2413");
+        plan.sort_by_fnsku();
+        assert_eq!(fnskus(&plan), "1234");
+        plan.sort_by_group();
+        assert_eq!(fnskus(&plan), "2413");
+        let groups = plan.iter().map(|e| e.try_group_name()).collect::<Vec<_>>();
+        assert_eq!(groups, [None, None, Some("GroupA"), Some("GroupB")]);
+    }
+    #[test]
+    fn display_summary() {
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,50,Packed,,,10,12,10,8,20,5",
+            "2,bad-fnsku,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        let report = plan.summarize().to_string();
+        assert_eq!(
+            report,
+            "2 SKUs, 62 units\n1 packed entry in 5 cases\n1 loose entry\n\
+             Invalid FNSKUs: bad-fnsku"
+        );
+    }
+    #[test]
+    fn total_cube() {
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,50,Packed,,,10,12,12,12,20,5",
+            "2,X001AAAAA2,6,Packed,,,2,24,12,6,20,3",
+            "3,X001AAAAA3,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        // 5 * 1728 + 3 * 1728
+        assert_eq!(plan.total_cube_inches(), 13824);
+        assert_eq!(plan.total_cube_feet(), 8.0);
+    }
+    #[test]
+    fn duplicate_ids() {
+        let plan = plan_from_rows(&[
+            "7,X001AAAAA1,50,Packed,,,10,12,10,8,20,5",
+            "8,X001AAAAA2,12,Loose,GroupA,0.5,,,,,,",
+            "7,X001AAAAA3,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        assert_eq!(plan.duplicate_ids(), vec![7]);
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        assert!(plan.duplicate_ids().is_empty());
+    }
+    #[test]
+    fn validate_reports_every_issue() {
+        let csv = format!(
+            "{}\n{}\n{}\n{}\n{}\n",
+            PLAN_HEADERS.join(","),
+            "1,X001AAAAA1,50,Packed,,,10,30,10,8,20,5",
+            "1,BADFNSKU,12,Loose,GroupA,0.5,,,,,,",
+            "2,X001AAAAA2,50,Packed,,,10,12,10,8,20,4",
+            "3,X001AAAAA3,45,Packed,,,10,12,10,8,20,",
+        );
+        let mut builder = PlanBuilder::from_csv_reader(csv.as_bytes()).unwrap();
+        builder.keep_error = true;
+        let plan = builder.build().unwrap();
+        let issues = plan.validate();
+
+        let kinds = issues.iter().map(|i| i.kind()).collect::<Vec<IssueKind>>();
+        assert!(kinds.contains(&IssueKind::InvalidFnsku));
+        assert!(kinds.contains(&IssueKind::DuplicateId));
+        assert!(kinds.contains(&IssueKind::CartonLimit));
+        assert!(kinds.contains(&IssueKind::TotalCasesMismatch));
+        assert!(kinds.contains(&IssueKind::NonDivisibleCaseQt));
+
+        let oversize = issues
+            .iter()
+            .find(|i| i.kind() == IssueKind::CartonLimit)
+            .unwrap();
+        assert_eq!(oversize.try_fnsku(), Some("X001AAAAA1"));
+        assert!(oversize.get_message().contains("30 inch"));
+        let mismatch = issues
+            .iter()
+            .find(|i| i.kind() == IssueKind::TotalCasesMismatch)
+            .unwrap();
+        assert_eq!(mismatch.try_fnsku(), None);
+        assert!(mismatch.get_message().starts_with("Line "));
+
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        assert!(plan.validate().is_empty());
+    }
+    #[test]
+    fn suspicious_unit_weights() {
+        // A 60 lb case holding a single unit, likely a misplaced case weight
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,5,Packed,,,1,12,10,8,60,5",
+            "2,X001AAAAA2,50,Packed,,,10,12,10,8,20,5",
+            "3,X001AAAAA3,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        assert_eq!(plan.default_suspicious_weights(), vec!["X001AAAAA1"]);
+        assert!(plan.suspicious_weights(u32::MAX).is_empty());
+        assert_eq!(plan.suspicious_weights(100).len(), 3);
+    }
+    #[test]
+    fn case_counts() {
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,50,Packed,,,10,12,10,8,20,5",
+            "2,X001AAAAA1,30,Packed,,,10,12,10,8,20,3",
+            "3,X001AAAAA2,24,Packed,,,6,12,10,8,20,4",
+            "4,X001AAAAA3,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        assert_eq!(plan.total_cases().unwrap(), 12);
+        let by_fnsku = plan.cases_by_fnsku().unwrap();
+        assert_eq!(by_fnsku.len(), 2);
+        assert_eq!(by_fnsku.get("X001AAAAA1"), Some(&8));
+        assert_eq!(by_fnsku.get("X001AAAAA2"), Some(&4));
+        assert_eq!(by_fnsku.get("X001AAAAA3"), None);
+    }
+    #[test]
+    fn reconcile_boxed_units() {
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        assert_eq!(plan.carton_unit_total().unwrap(), plan.total_units());
+        assert!(plan.reconcile_units());
+
+        // The bare entry's units are not in any box
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,50,Packed,,,10,12,10,8,20,5",
+            "2,X001AAAAA2,12,Loose,GroupA,0.5,,,,,,",
+            "3,X001AAAAA3,7,,,,,,,,,",
+        ]);
+        assert_eq!(plan.carton_unit_total().unwrap(), 62);
+        assert!(!plan.reconcile_units());
+    }
+    #[test]
+    fn rank_heaviest_and_largest() {
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,50,Packed,,,10,12,10,8,20,5",
+            "2,X001AAAAA2,20,Packed,,,10,24,18,18,30,2",
+            "3,X001AAAAA3,500,Loose,GroupA,0.5,,,,,,",
+            "4,X001AAAAA4,7,,,,,,,,,",
+        ]);
+        // 500 loose units at 0.5 lb outweigh 5 cases at 20 lb
+        let heaviest = plan.heaviest_entries(2);
+        assert_eq!(heaviest.len(), 2);
+        assert_eq!(heaviest[0].get_fnsku(), "X001AAAAA3");
+        assert_eq!(heaviest[1].get_fnsku(), "X001AAAAA1");
+        assert_eq!(plan.heaviest_entries(10).len(), 3);
+
+        let largest = plan.largest_cases(10);
+        assert_eq!(largest.len(), 2);
+        assert_eq!(largest[0].get_fnsku(), "X001AAAAA2");
+        assert!(plan.largest_cases(0).is_empty());
+    }
+    #[test]
+    fn find_entry_by_id() {
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        let entry = plan.find_by_id(3).unwrap();
+        assert_eq!(entry.get_fnsku(), "X001AAAAA3");
+        assert_eq!(entry.get_units(), 25);
+        // Row 6 has no FNSKU, so it never made it into the plan
+        assert!(plan.find_by_id(6).is_none());
+        assert!(plan.find_by_id(999).is_none());
+    }
+    #[test]
+    fn append_csv_path_twice() {
+        static TEST_PLAN: &str = "tests/data/STAPlan.csv";
+        let mut plan = Plan::default();
+        assert_eq!(plan.append_csv_path(TEST_PLAN).unwrap(), 12);
+        assert_eq!(plan.append_csv_path(TEST_PLAN).unwrap(), 12);
+        assert_eq!(plan.len(), 24);
+        // An empty plan counts as sorted, so it stays sorted
+        assert!(plan.entries.is_sorted_by_key(sort_key));
+        assert!(plan.append_csv_path("tests/data/missing.csv").is_err());
+        assert_eq!(plan.len(), 24);
+    }
+    #[test]
+    fn mixed_pack_types() {
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,50,Packed,,,10,12,10,8,20,5",
+            "2,X001AAAAA1,12,Loose,GroupA,0.5,,,,,,",
+            "3,X001AAAAA2,12,Loose,GroupA,0.5,,,,,,",
+            "4,X001AAAAA2,6,Loose,GroupB,0.5,,,,,,",
+        ]);
+        assert_eq!(plan.mixed_pack_type_fnskus(), vec!["X001AAAAA1"]);
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        assert!(plan.mixed_pack_type_fnskus().is_empty());
+    }
+    #[test]
+    fn rebalance_heavy_cases() {
+        // 60 lb cases of 10 units, 6 lb per unit
+        let mut plan = plan_from_rows(&[
+            "1,X001AAAAA1,50,Packed,,,10,12,10,8,60,5",
+            "2,X001AAAAA2,50,Packed,,,10,12,10,8,20,5",
+            "3,X001AAAAA3,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        let units = plan.total_units();
+        plan.rebalance_cases(FBA_MAX_CARTON_GRAMS).unwrap();
+
+        assert_eq!(plan.total_units(), units);
+        assert!(plan.fba_carton_violations().is_empty());
+        let heavy = plan.filter_fnsku("X001AAAAA1");
+        let cases = heavy.iter().map(|e| e.to_string()).collect::<Vec<String>>();
+        assert_eq!(
+            cases,
+            vec![
+                "X001AAAAA1 x48 (6 cases of 8)",
+                "X001AAAAA1 x2 (1 case of 2)"
+            ]
+        );
+        let light = plan.filter_fnsku("X001AAAAA2");
+        assert_eq!(
+            light.entries[0].to_string(),
+            "X001AAAAA2 x50 (5 cases of 10)"
+        );
+        assert_eq!(plan.len(), 4);
+        assert_eq!(heavy.entries[1].get_id(), 4);
+        assert!(plan.validate().is_empty());
+
+        // 150 lb cases of 10 units, only 3 units fit, leaving 1 over
+        let mut plan = plan_from_rows(&["1,X001AAAAA1,70,Packed,,,10,12,10,8,150,7"]);
+        plan.rebalance_cases(FBA_MAX_CARTON_GRAMS).unwrap();
+        let cases = plan
+            .entries
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(
+            cases,
+            vec![
+                "X001AAAAA1 x69 (23 cases of 3)",
+                "X001AAAAA1 x1 (1 case of 1)"
+            ]
+        );
+        let grams = plan
+            .entries
+            .iter()
+            .map(|e| e.try_case_gram_weight().unwrap())
+            .collect::<Vec<u32>>();
+        assert!(grams.iter().all(|&g| g > 0 && g <= FBA_MAX_CARTON_GRAMS));
+        assert_eq!(plan.entries[1].get_id(), 2);
+        assert!(plan.validate().is_empty());
+    }
+    #[test]
+    fn edit_plan_in_place() {
+        let mut plan = load_csv_into_builder().unwrap().build().unwrap();
+        assert_eq!(plan.remove_by_fnsku("X001AAAAA2"), 1);
+        assert_eq!(plan.len(), 11);
+        assert!(!plan.contains_fnsku("X001AAAAA2"));
+        assert_eq!(plan.remove_by_fnsku("X001AAAAA2"), 0);
+
+        // X001AAAAA1 is packed 10 to a case
+        plan.set_units("X001AAAAA1", 30).unwrap();
+        assert_eq!(plan.filter_fnsku("X001AAAAA1").total_units(), 30);
+        assert!(matches!(
+            plan.set_units("X001AAAAA1", 45),
+            Err(ErrorKind::NonDivisibleCaseQt)
+        ));
+        assert!(matches!(
+            plan.set_units("X000000000", 1),
+            Err(ErrorKind::FnskuNotFound { .. })
+        ));
+
+        let mut plan = plan_from_rows(&[
+            "1,X001AAAAA1,12,Loose,GroupA,0.5,,,,,,",
+            "2,X001AAAAA1,6,Loose,GroupB,0.5,,,,,,",
+        ]);
+        assert!(matches!(
+            plan.set_units("X001AAAAA1", 4),
+            Err(ErrorKind::AmbiguousFnsku { count: 2, .. })
+        ));
+        assert_eq!(plan.total_units(), 18);
+    }
+    #[test]
+    fn filter_fnsku_and_retain_group() {
+        let plan = load_csv_into_builder().unwrap().build().unwrap();
+        let single = plan.filter_fnsku("X001AAAAA1");
+        assert_eq!(single.len(), 1);
+        assert!(single.iter().all(|e| e.get_fnsku() == "X001AAAAA1"));
+        assert!(plan.filter_fnsku("X000000000").is_empty());
+
+        let group = plan.retain_group("GroupA");
+        assert_eq!(group.len(), 4);
+        assert!(group.iter().all(|e| e.try_group_name() == Some("GroupA")));
+        assert_eq!(plan.len(), 12);
+    }
+    #[test]
+    fn total_gram_weight_overflow_is_none() {
+        // Each entry fits within u32, but their sum does not
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,1000,Loose,GroupA,5000,,,,,,",
+            "2,X001AAAAA2,1000,Loose,GroupA,5000,,,,,,",
+        ]);
+        assert!(plan.entries[0].try_total_gram_weight().is_some());
+        assert_eq!(plan.total_gram_weight(), None);
+    }
+    #[test]
+    fn builder_from_csv_str() {
+        let csv = "\
+Info,FNSKU,Quantity,Pack Type,Staging Group,Unit Weight,Case QT,Case Length,Case Width,Case Height,Case Weight,Total Cases
+1,X001AAAAA1,50,Packed,,,10,12,10,8,20,5
+";
+        let plan = PlanBuilder::from_csv_str(csv).unwrap().build().unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan.total_units(), 50);
+    }
+    #[test]
+    fn fluent_tab_delimited_kilograms() {
+        let tsv = format!(
+            "{}\n{}\n{}\n",
+            PLAN_HEADERS.join("\t"),
+            "1\tX001AAAAA1\t50\tPacked\t\t\t10\t12\t10\t8\t9.5\t5",
+            "2\tX001AAAAA2\t12\tLoose\tGroupA\t0.25\t\t\t\t\t\t",
+        );
+        let plan = PlanBuilder::default()
+            .with_keep_error(true)
+            .with_delimiter(b'\t')
+            .with_weight_unit(WeightUnit::Kilograms)
+            .read_csv_reader(tsv.as_bytes())
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(plan.len(), 2);
+        assert!(plan.errors().is_empty());
+        assert_eq!(plan.entries[0].try_case_gram_weight(), Some(9500));
+        assert_eq!(plan.entries[1].try_unit_gram_weight(), Some(250));
+    }
+    #[test]
+    fn from_csv_reader_matches_path() {
+        static TEST_PLAN: &str = "tests/data/STAPlan.csv";
+        let bytes = std::fs::read(TEST_PLAN).unwrap();
+        let from_reader = PlanBuilder::from_csv_reader(&bytes[..]).unwrap();
+        let from_path = load_csv_into_builder().unwrap();
+        assert_eq!(from_reader.entries.len(), from_path.entries.len());
+    }
+    #[test]
+    fn group_loose_entries_by_staging_group() {
+        let plan = plan_from_rows(&[
+            "1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            "2,X001ABCDE3,12,Loose,GroupA,0.5,,,,,,",
+            "3,X001ABCDE4,6,Loose,GroupB,1.25,,,,,,",
+            "4,X001ABCDE5,4,Loose,GroupA,0.75,,,,,,",
+        ]);
+        let groups = plan.group_by_staging_group();
+        let loose_count = plan.entries.iter().filter(|e| e.is_loose()).count();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups.values().map(Vec::len).sum::<usize>(), loose_count);
+        assert_eq!(groups["GroupA"].len(), 2);
+    }
+    #[test]
+    fn split_plan_by_pack_type() {
+        let builder = load_csv_into_builder().unwrap();
+        let plan = builder.build().unwrap();
+        let len = plan.entries.len();
+        let (packed, loose, bare) = plan.split_by_pack_type();
+        assert_eq!(packed.len() + loose.len() + bare.len(), len);
+        assert!(packed.entries.iter().all(|e| e.is_packed()));
+        assert!(loose.entries.iter().all(|e| e.is_loose()));
+
+        let plan = plan_from_rows(&[
+            "1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            "2,X001ABCDE3,6,Loose,GroupA,0.5,,,,,,",
+            "3,X001ABCDE4,4,,,,,,,,,",
+        ]);
+        let (packed, loose, bare) = plan.split_by_pack_type();
+        assert_eq!((packed.len(), loose.len(), bare.len()), (1, 1, 1));
+        assert!(bare.entries.iter().all(|e| e.is_bare()));
+    }
+    #[test]
+    fn merge_and_consolidate_packed() {
+        let mut plan = plan_from_rows(&["1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5"]);
+        let other = plan_from_rows(&["2,X001ABCDE2,30,Packed,,,10,8,12,10,20,3"]);
+        plan.merge(other);
+        assert_eq!(plan.entries.len(), 2);
+        plan.consolidate();
+        assert_eq!(plan.entries.len(), 1);
+        assert_eq!(plan.entries[0].get_units(), 80);
+    }
+    #[test]
+    fn consolidate_keeps_pack_types_apart() {
+        let mut plan = plan_from_rows(&[
+            "1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            "2,X001ABCDE2,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        plan.consolidate();
+        assert_eq!(plan.entries.len(), 2);
+    }
+    #[test]
+    fn diff_revised_plan() {
+        let original = plan_from_rows(&[
+            "1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            "2,X001ABCDE3,12,Loose,GroupA,0.5,,,,,,",
+            "3,X001ABCDE4,6,Loose,GroupB,1.25,,,,,,",
+        ]);
+        let revised = plan_from_rows(&[
+            "1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            "2,X001ABCDE3,8,Loose,GroupA,0.5,,,,,,",
+            "3,X001ABCDE5,4,Loose,GroupB,0.75,,,,,,",
+        ]);
+        let diff = original.diff(&revised);
+        assert_eq!(diff.added(), ["X001ABCDE5"]);
+        assert_eq!(diff.removed(), ["X001ABCDE4"]);
+        assert_eq!(diff.changed(), [("X001ABCDE3".to_string(), 12, 8)]);
+        assert!(original.diff(&original).is_empty());
+    }
+    #[test]
+    fn summarize_built_plan() {
+        let builder = load_csv_into_builder().unwrap();
+        let summary = builder.build().unwrap().summarize();
+        assert_eq!(summary.sku_count(), 12);
+        assert_eq!(summary.total_units(), 174);
+        assert_eq!(summary.packed_count(), 3);
+        assert_eq!(summary.loose_count(), 9);
+        assert_eq!(summary.total_cases(), 13);
+        assert!(summary.invalid_fnskus().is_empty());
+        assert!(!summary.has_invalid());
+    }
+    #[test]
+    fn summary_unit_shares() {
+        let plan = plan_from_rows(&[
+            "1,X001AAAAA1,80,Packed,,,10,12,10,8,20,8",
+            "2,X001AAAAA2,20,Loose,GroupA,0.5,,,,,,",
+        ]);
+        let summary = plan.summarize();
+        assert!((summary.packed_unit_share() - 0.8).abs() < f64::EPSILON);
+        assert!((summary.loose_unit_share() - 0.2).abs() < f64::EPSILON);
+
+        let empty = Plan::default().summarize();
+        assert_eq!(empty.packed_unit_share(), 0.0);
+        assert_eq!(empty.loose_unit_share(), 0.0);
+    }
+    #[test]
+    fn summary_lists_invalid_fnskus() {
+        let plan = plan_from_rows(&[
+            "1,X001ABCDE,12,Loose,GroupA,0.5,,,,,,",
+            "2,X001ABCDE22,6,Loose,GroupA,0.5,,,,,,",
+            "3,X001ABCDE2,6,Loose,GroupA,0.5,,,,,,",
+            "4,x001abcde2,6,Loose,GroupA,0.5,,,,,,",
+        ]);
+        let summary = plan.summarize();
+        assert!(summary.has_invalid());
+        let expect = ["X001ABCDE", "X001ABCDE22", "x001abcde2"];
+        assert_eq!(summary.invalid_fnskus(), expect);
+    }
+    #[test]
+    fn expand_packed_entry_into_cartons() {
+        let plan = plan_from_rows(&[
+            "1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            "2,X001ABCDE3,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        let cartons = plan.cartons().unwrap();
+        assert_eq!(cartons.len(), 5);
+        assert!(cartons.iter().all(|c| c.get_units() == 10));
+        assert!(cartons.iter().all(|c| c.get_fnsku() == "X001ABCDE2"));
+    }
+    #[test]
+    fn pack_loose_units_under_cap() {
+        // 30 units at 227g and 10 units at 454g
+        let plan = plan_from_rows(&[
+            "1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            "2,X001ABCDE3,30,Loose,GroupA,0.5,,,,,,",
+            "3,X001ABCDE4,10,Loose,GroupB,1.0,,,,,,",
+        ]);
+        let group_a_grams = 30 * 227;
+        let max_box_grams = group_a_grams / 3;
+        let boxes = plan.pack_loose(max_box_grams);
+        let group_a = boxes.iter().filter(|b| b.get_group() == "GroupA");
+        assert!(group_a.count() >= 3);
+        assert!(boxes.iter().all(|b| b.get_gram_weight() <= max_box_grams));
+        let packed_units: u32 = boxes
+            .iter()
+            .flat_map(|b| b.get_contents())
+            .map(|(_, units)| units)
+            .sum();
+        assert_eq!(packed_units, 40);
+    }
+    #[test]
+    fn box_contents_tsv_has_line_per_carton() {
+        let plan = plan_from_rows(&[
+            "1,X001ABCDE2,50,Packed,,,10,12,10,8,20,5",
+            "2,X001ABCDE3,36,Packed,,,12,16,12,10,24,3",
+            "3,X001ABCDE4,12,Loose,GroupA,0.5,,,,,,",
+        ]);
+        let mut buf = Vec::new();
+        plan.to_box_contents_tsv(&mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        let mut lines = output.lines();
+        let header = "FNSKU\tQuantity\tBox Number\tBox Weight (lb)\t\
+            Box Length (in)\tBox Width (in)\tBox Height (in)";
+        assert_eq!(lines.next(), Some(header));
+        assert_eq!(lines.next(), Some("X001ABCDE2\t10\t1\t20.00\t12\t10\t8"));
+        assert_eq!(lines.count() + 1, 8);
+    }
+    #[test]
+    fn plan_to_json() {
+        let builder = load_csv_into_builder().unwrap();
+        let plan = builder.build().unwrap();
+        let pretty = plan.to_json_pretty().unwrap();
+        let value = serde_json::from_str::<serde_json::Value>(&pretty).unwrap();
+        let entries = value["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), plan.entries.len());
+
+        let compact = plan.to_json().unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&compact).unwrap(),
+            value
+        );
+
+        let mut buf = Vec::new();
+        plan.write_json(&mut buf).unwrap();
+        assert_eq!(buf, pretty.as_bytes());
+    }
+    #[test]
+    fn remove_only_missing_fnsku_errors() {
+        let row = "1,X001ABCDE2,12,Loose,GroupA,0.5,,,,,,";
+        let record = csv::StringRecord::from(row.split(',').collect::<Vec<_>>());
+        let mut builder = PlanBuilder::default();
+        builder.push(2, Entry::from_csv_record(record));
+        builder.push(3, Err(ErrorKind::MissingFnsku));
+        builder.push(4, Err(ErrorKind::MissingId));
+        builder.remove_entries_without_fnskus();
+        assert_eq!(builder.entries.len(), 2);
+        assert!(builder.entries[0].is_ok());
+        let error = builder.errors().next().unwrap();
+        assert!(matches!(error.kind(), ErrorKind::MissingId));
+    }
+    #[test]
+    fn errors_report_their_line() {
+        static TEST_PLAN: &str = "tests/data/STAPlanBadRow.csv";
+        let builder = PlanBuilder::from_csv_path(TEST_PLAN).unwrap();
+        let errors = builder.errors().collect::<Vec<&RowError>>();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line(), 3);
+    }
 }
@@ -0,0 +1,41 @@
+/**
+Returns `true` if `fnsku` follows Amazon's FNSKU format.
+
+A valid FNSKU is 10 characters long, begins with an uppercase letter
+(commonly `X`), and is otherwise made up of uppercase letters and digits.
+*/
+pub fn is_valid_fnsku(fnsku: &str) -> bool {
+    let mut chars = fnsku.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    fnsku.chars().count() == 10
+        && first.is_ascii_uppercase()
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_fnsku() {
+        assert!(is_valid_fnsku("X001ABCDE2"));
+    }
+    #[test]
+    fn lowercase_fnsku() {
+        assert!(!is_valid_fnsku("x001abcde2"));
+        assert!(!is_valid_fnsku("X001aBCDE2"));
+    }
+    #[test]
+    fn wrong_length_fnsku() {
+        assert!(!is_valid_fnsku("X001ABCDE"));
+        assert!(!is_valid_fnsku("X001ABCDE22"));
+        assert!(!is_valid_fnsku(""));
+    }
+    #[test]
+    fn punctuation_fnsku() {
+        assert!(!is_valid_fnsku("!!!!!!!!!!"));
+        assert!(!is_valid_fnsku("0001ABCDE2"));
+    }
+}
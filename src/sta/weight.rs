@@ -0,0 +1,115 @@
+/// The exact number of grams in a pound.
+pub const GRAMS_PER_POUND: f64 = 453.59237;
+
+/**
+Converts `pounds` into grams, rounding half up to the nearest gram.
+
+Every pound weight read from a plan goes through this, so the same weight
+always yields the same grams. Dimensional weight is the exception, it is
+derived from [`GRAMS_PER_POUND`] directly and rounded up instead.
+*/
+pub fn lbs_to_grams(pounds: f32) -> u32 {
+    WeightUnit::Pounds.to_grams(pounds)
+}
+
+/**
+Converts `grams` into pounds, rounded to two decimals.
+
+The inverse of [`lbs_to_grams`], used whenever a weight is written back out.
+*/
+pub fn grams_to_lbs(grams: u32) -> f64 {
+    WeightUnit::Pounds.convert_grams(grams)
+}
+
+/**
+Converts `grams` into pounds as an `f32`, rounded to two decimals, e.g. to
+compare an exported weight against the sheet it came from.
+
+Exports with any other [`WeightUnit`] go through [`WeightUnit::convert_grams`].
+*/
+pub fn to_pounds(grams: u32) -> f32 {
+    grams_to_lbs(grams) as f32
+}
+
+/**
+The unit a weight is recorded in within a shipping plan.
+
+Defaults to [`WeightUnit::Pounds`], which is what plans have historically
+been written in.
+*/
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WeightUnit {
+    #[default]
+    Pounds,
+    Kilograms,
+    Ounces,
+    Grams,
+}
+impl WeightUnit {
+    /// Returns the number of grams in a single unit of this [`WeightUnit`].
+    fn grams_per_unit(&self) -> f64 {
+        match self {
+            WeightUnit::Pounds => GRAMS_PER_POUND,
+            WeightUnit::Kilograms => 1000.0,
+            WeightUnit::Ounces => GRAMS_PER_POUND / 16.0,
+            WeightUnit::Grams => 1.0,
+        }
+    }
+    /// Converts `value` of this [`WeightUnit`] into grams, rounding half up.
+    pub fn to_grams(&self, value: f32) -> u32 {
+        // f64::round rounds half away from zero, which is half up for weights
+        (value as f64 * self.grams_per_unit()).round() as u32
+    }
+    /**
+    Converts `grams` into this [`WeightUnit`], rounded to two decimals.
+
+    The inverse of [`WeightUnit::to_grams`], used whenever a weight is
+    written back out.
+    */
+    pub fn convert_grams(&self, grams: u32) -> f64 {
+        (grams as f64 / self.grams_per_unit() * 100.0).round() / 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kilograms_to_grams() {
+        assert_eq!(WeightUnit::Kilograms.to_grams(1.0), 1000);
+    }
+    #[test]
+    fn ounces_to_grams() {
+        assert_eq!(WeightUnit::Ounces.to_grams(16.0), 454);
+    }
+    #[test]
+    fn to_pounds_rounds_to_two_decimals() {
+        assert_eq!(to_pounds(454), 1.0);
+        assert_eq!(to_pounds(lbs_to_grams(2.35)), 2.35);
+        assert_eq!(WeightUnit::Grams.convert_grams(1000), 1000.0);
+    }
+    #[test]
+    fn grams_to_pounds() {
+        assert_eq!(grams_to_lbs(454), 1.0);
+        assert_eq!(grams_to_lbs(lbs_to_grams(2.35)), 2.35);
+    }
+    #[test]
+    fn convert_grams_to_each_unit() {
+        assert_eq!(WeightUnit::Pounds.convert_grams(454), 1.0);
+        assert_eq!(WeightUnit::Kilograms.convert_grams(1500), 1.5);
+        assert_eq!(WeightUnit::Ounces.convert_grams(454), 16.01);
+        assert_eq!(WeightUnit::Grams.convert_grams(1000), 1000.0);
+    }
+    #[test]
+    fn pounds_round_half_up() {
+        assert_eq!(lbs_to_grams(1.0), 454);
+        assert_eq!(lbs_to_grams(0.5), 227);
+        assert_eq!(lbs_to_grams(0.0), 0);
+    }
+    #[test]
+    fn default_is_pounds() {
+        assert_eq!(WeightUnit::default(), WeightUnit::Pounds);
+        assert_eq!(WeightUnit::default().to_grams(1.0), 454);
+    }
+}
@@ -0,0 +1,68 @@
+use tbs::sta::plan::{Plan, PlanBuilder};
+use tbs::sta::weight::WeightUnit;
+
+static TEST_PLAN_RECORD: &str = "tests/data/STAPlan.csv";
+
+#[test]
+fn sort_plan_from_outside_the_crate() {
+    let mut plan = Plan::from_csv_path(TEST_PLAN_RECORD).unwrap();
+    assert!(plan.contains_fnsku("X001AAAAA1"));
+
+    plan.sort_by_fnsku();
+    let fnskus = plan.iter().map(|e| e.get_fnsku()).collect::<Vec<&str>>();
+    assert!(fnskus.is_sorted());
+    let len = fnskus.len();
+    assert_eq!(plan.len(), len);
+    assert!(!plan.is_empty());
+
+    plan.sort_by_group();
+    plan.sort();
+    assert_eq!(plan.iter().count(), len);
+}
+#[test]
+fn fluent_tab_delimited_kilogram_plan() {
+    let plan = PlanBuilder::default()
+        .with_delimiter(b'\t')
+        .with_weight_unit(WeightUnit::Kilograms)
+        .read_csv_reader(
+            "Info\tFNSKU\tQuantity\tPack Type\tStaging Group\tUnit Weight\n\
+             1\tX001AAAAA1\t12\tLoose\tGroupA\t0.5\n"
+                .as_bytes(),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+    let entry = plan.iter().next().unwrap();
+    assert_eq!(entry.get_units(), 12);
+    assert_eq!(entry.try_unit_gram_weight(), Some(500));
+}
+#[test]
+fn kept_errors_reach_the_caller() {
+    let builder = PlanBuilder::default()
+        .with_keep_error(true)
+        .read_csv_path(TEST_PLAN_RECORD)
+        .unwrap();
+    let lines = builder.errors().map(|e| e.line()).collect::<Vec<u64>>();
+    let plan = builder.build().unwrap();
+    let kept = plan.errors().iter().map(|e| e.line()).collect::<Vec<u64>>();
+    // Rows missing an FNSKU are blank rows, only the unknown pack type is kept
+    assert_eq!(lines, vec![7, 12, 16]);
+    assert_eq!(kept, vec![16]);
+}
+#[test]
+fn report_on_a_plan_from_outside_the_crate() {
+    let plan = Plan::from_csv_path(TEST_PLAN_RECORD).unwrap();
+    let summary = plan.summarize();
+    assert_eq!(summary.total_units(), plan.total_units());
+    assert_eq!(summary.total_cases(), plan.total_cases().unwrap());
+    assert!(plan.diff(&plan).is_empty());
+    assert!(plan.to_json().unwrap().starts_with("{\"entries\":["));
+
+    let mut csv = Vec::new();
+    plan.write_csv(&mut csv).unwrap();
+    let reread = PlanBuilder::from_csv_reader(csv.as_slice())
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(reread.len(), plan.len());
+}
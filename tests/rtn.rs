@@ -0,0 +1,30 @@
+use tbs::rtn::reconcile::reconcile;
+use tbs::rtn::removals::{CsvRemShipParser, RemovalReport};
+use tbs::rtn::returns::ReturnsBucket;
+use tbs::sta::plan::Plan;
+
+static TEST_REMOVAL_SHIPMENT_RECORD: &str = "tests/data/RemovalShipments.csv";
+static TEST_CUSTOMER_RETURN_RECORD: &str = "tests/data/CustomerReturns.csv";
+static TEST_PLAN_RECORD: &str = "tests/data/STAPlan.csv";
+
+#[test]
+fn load_reports_from_outside_the_crate() {
+    let removals = RemovalReport::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+    let returns = ReturnsBucket::from_csv_path(TEST_CUSTOMER_RETURN_RECORD).unwrap();
+    assert_eq!(removals.len(), 5);
+    assert_eq!(returns.total_units(), 13);
+    assert_eq!(returns.orphan_returns(&removals).len(), 3);
+
+    let parsers = CsvRemShipParser::from_csv_path(TEST_REMOVAL_SHIPMENT_RECORD).unwrap();
+    let reconciliation = reconcile(&parsers, &returns);
+    assert_eq!(reconciliation.orders().len(), 4);
+}
+
+#[test]
+fn unexpected_returns_against_a_plan() {
+    let returns = ReturnsBucket::from_csv_path(TEST_CUSTOMER_RETURN_RECORD).unwrap();
+    let plan = Plan::from_csv_path(TEST_PLAN_RECORD).unwrap();
+    let unexpected = returns.unexpected_returns(&plan);
+    assert_eq!(unexpected.len(), 1);
+    assert_eq!(unexpected[0].get_fnsku(), "X009ZZZZZ9");
+}